@@ -219,3 +219,6 @@ getMappingSlotAt(address,bytes32,uint256)
 getMappingKeyAndParentOf(address,bytes32)
 
 sleep(uint256)
+
+zkStorageKey(address,bytes32)(bytes32)
+zkComputeCreate2Address(address,bytes32,bytes32,bytes32)(address)
@@ -4809,6 +4809,86 @@ pub mod hevm {
                         },
                     ],
                 ),
+                (
+                    ::std::borrow::ToOwned::to_owned("zkComputeCreate2Address"),
+                    ::std::vec![
+                        ::ethers_core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned(
+                                "zkComputeCreate2Address",
+                            ),
+                            inputs: ::std::vec![
+                                ::ethers_core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers_core::abi::ethabi::ParamType::Address,
+                                    internal_type: ::core::option::Option::None,
+                                },
+                                ::ethers_core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers_core::abi::ethabi::ParamType::FixedBytes(
+                                        32usize,
+                                    ),
+                                    internal_type: ::core::option::Option::None,
+                                },
+                                ::ethers_core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers_core::abi::ethabi::ParamType::FixedBytes(
+                                        32usize,
+                                    ),
+                                    internal_type: ::core::option::Option::None,
+                                },
+                                ::ethers_core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers_core::abi::ethabi::ParamType::FixedBytes(
+                                        32usize,
+                                    ),
+                                    internal_type: ::core::option::Option::None,
+                                },
+                            ],
+                            outputs: ::std::vec![
+                                ::ethers_core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers_core::abi::ethabi::ParamType::Address,
+                                    internal_type: ::core::option::Option::None,
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers_core::abi::ethabi::StateMutability::NonPayable,
+                        },
+                    ],
+                ),
+                (
+                    ::std::borrow::ToOwned::to_owned("zkStorageKey"),
+                    ::std::vec![
+                        ::ethers_core::abi::ethabi::Function {
+                            name: ::std::borrow::ToOwned::to_owned("zkStorageKey"),
+                            inputs: ::std::vec![
+                                ::ethers_core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers_core::abi::ethabi::ParamType::Address,
+                                    internal_type: ::core::option::Option::None,
+                                },
+                                ::ethers_core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers_core::abi::ethabi::ParamType::FixedBytes(
+                                        32usize,
+                                    ),
+                                    internal_type: ::core::option::Option::None,
+                                },
+                            ],
+                            outputs: ::std::vec![
+                                ::ethers_core::abi::ethabi::Param {
+                                    name: ::std::string::String::new(),
+                                    kind: ::ethers_core::abi::ethabi::ParamType::FixedBytes(
+                                        32usize,
+                                    ),
+                                    internal_type: ::core::option::Option::None,
+                                },
+                            ],
+                            constant: ::core::option::Option::None,
+                            state_mutability: ::ethers_core::abi::ethabi::StateMutability::NonPayable,
+                        },
+                    ],
+                ),
             ]),
             events: ::std::collections::BTreeMap::new(),
             errors: ::std::collections::BTreeMap::new(),
@@ -6867,6 +6947,28 @@ pub mod hevm {
                 .method_hash([97, 157, 137, 127], (p0, p1))
                 .expect("method not found (this should never happen)")
         }
+        ///Calls the contract's `zkComputeCreate2Address` (0x381ed2e7) function
+        pub fn zk_compute_create_2_address(
+            &self,
+            p0: ::ethers_core::types::Address,
+            p1: [u8; 32],
+            p2: [u8; 32],
+            p3: [u8; 32],
+        ) -> ::ethers_contract::builders::ContractCall<M, ::ethers_core::types::Address> {
+            self.0
+                .method_hash([56, 30, 210, 231], (p0, p1, p2, p3))
+                .expect("method not found (this should never happen)")
+        }
+        ///Calls the contract's `zkStorageKey` (0x20d607dc) function
+        pub fn zk_storage_key(
+            &self,
+            p0: ::ethers_core::types::Address,
+            p1: [u8; 32],
+        ) -> ::ethers_contract::builders::ContractCall<M, [u8; 32]> {
+            self.0
+                .method_hash([32, 214, 7, 220], (p0, p1))
+                .expect("method not found (this should never happen)")
+        }
     }
     impl<M: ::ethers_providers::Middleware> From<::ethers_contract::Contract<M>>
     for HEVM<M> {
@@ -9705,6 +9807,40 @@ pub mod hevm {
     )]
     #[ethcall(name = "writeLine", abi = "writeLine(string,string)")]
     pub struct WriteLineCall(pub ::std::string::String, pub ::std::string::String);
+    ///Container type for all input parameters for the `zkComputeCreate2Address` function with signature `zkComputeCreate2Address(address,bytes32,bytes32,bytes32)` and selector `0x381ed2e7`
+    #[derive(
+        Clone,
+        ::ethers_contract::EthCall,
+        ::ethers_contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(
+        name = "zkComputeCreate2Address",
+        abi = "zkComputeCreate2Address(address,bytes32,bytes32,bytes32)"
+    )]
+    pub struct ZkComputeCreate2AddressCall(
+        pub ::ethers_core::types::Address,
+        pub [u8; 32],
+        pub [u8; 32],
+        pub [u8; 32],
+    );
+    ///Container type for all input parameters for the `zkStorageKey` function with signature `zkStorageKey(address,bytes32)` and selector `0x20d607dc`
+    #[derive(
+        Clone,
+        ::ethers_contract::EthCall,
+        ::ethers_contract::EthDisplay,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    #[ethcall(name = "zkStorageKey", abi = "zkStorageKey(address,bytes32)")]
+    pub struct ZkStorageKeyCall(pub ::ethers_core::types::Address, pub [u8; 32]);
     ///Container type for all of the contract's call
     #[derive(Clone, ::ethers_contract::EthAbiType, Debug, PartialEq, Eq, Hash)]
     pub enum HEVMCalls {
@@ -9905,6 +10041,8 @@ pub mod hevm {
         WriteJson0(WriteJson0Call),
         WriteJson1(WriteJson1Call),
         WriteLine(WriteLineCall),
+        ZkComputeCreate2Address(ZkComputeCreate2AddressCall),
+        ZkStorageKey(ZkStorageKeyCall),
     }
     impl ::ethers_core::abi::AbiDecode for HEVMCalls {
         fn decode(
@@ -10745,6 +10883,16 @@ pub mod hevm {
                 = <WriteLineCall as ::ethers_core::abi::AbiDecode>::decode(data) {
                 return Ok(Self::WriteLine(decoded));
             }
+            if let Ok(decoded)
+                = <ZkComputeCreate2AddressCall as ::ethers_core::abi::AbiDecode>::decode(
+                    data,
+                ) {
+                return Ok(Self::ZkComputeCreate2Address(decoded));
+            }
+            if let Ok(decoded)
+                = <ZkStorageKeyCall as ::ethers_core::abi::AbiDecode>::decode(data) {
+                return Ok(Self::ZkStorageKey(decoded));
+            }
             Err(::ethers_core::abi::Error::InvalidData.into())
         }
     }
@@ -11228,6 +11376,12 @@ pub mod hevm {
                 Self::WriteLine(element) => {
                     ::ethers_core::abi::AbiEncode::encode(element)
                 }
+                Self::ZkComputeCreate2Address(element) => {
+                    ::ethers_core::abi::AbiEncode::encode(element)
+                }
+                Self::ZkStorageKey(element) => {
+                    ::ethers_core::abi::AbiEncode::encode(element)
+                }
             }
         }
     }
@@ -11451,6 +11605,10 @@ pub mod hevm {
                 Self::WriteJson0(element) => ::core::fmt::Display::fmt(element, f),
                 Self::WriteJson1(element) => ::core::fmt::Display::fmt(element, f),
                 Self::WriteLine(element) => ::core::fmt::Display::fmt(element, f),
+                Self::ZkComputeCreate2Address(element) => {
+                    ::core::fmt::Display::fmt(element, f)
+                }
+                Self::ZkStorageKey(element) => ::core::fmt::Display::fmt(element, f),
             }
         }
     }
@@ -12439,6 +12597,16 @@ pub mod hevm {
             Self::WriteLine(value)
         }
     }
+    impl ::core::convert::From<ZkComputeCreate2AddressCall> for HEVMCalls {
+        fn from(value: ZkComputeCreate2AddressCall) -> Self {
+            Self::ZkComputeCreate2Address(value)
+        }
+    }
+    impl ::core::convert::From<ZkStorageKeyCall> for HEVMCalls {
+        fn from(value: ZkStorageKeyCall) -> Self {
+            Self::ZkStorageKey(value)
+        }
+    }
     ///Container type for all return fields from the `accesses` function with signature `accesses(address)` and selector `0x65bc9481`
     #[derive(
         Clone,
@@ -13676,6 +13844,30 @@ pub mod hevm {
         Hash
     )]
     pub struct SnapshotReturn(pub ::ethers_core::types::U256);
+    ///Container type for all return fields from the `zkComputeCreate2Address` function with signature `zkComputeCreate2Address(address,bytes32,bytes32,bytes32)` and selector `0x381ed2e7`
+    #[derive(
+        Clone,
+        ::ethers_contract::EthAbiType,
+        ::ethers_contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct ZkComputeCreate2AddressReturn(pub ::ethers_core::types::Address);
+    ///Container type for all return fields from the `zkStorageKey` function with signature `zkStorageKey(address,bytes32)` and selector `0x20d607dc`
+    #[derive(
+        Clone,
+        ::ethers_contract::EthAbiType,
+        ::ethers_contract::EthAbiCodec,
+        Default,
+        Debug,
+        PartialEq,
+        Eq,
+        Hash
+    )]
+    pub struct ZkStorageKeyReturn(pub [u8; 32]);
     ///`DirEntry(string,string,uint64,bool,bool)`
     #[derive(
         Clone,
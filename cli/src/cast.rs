@@ -344,6 +344,20 @@ async fn main() -> eyre::Result<()> {
         Subcommands::SendTx(cmd) => cmd.run().await?,
         Subcommands::ZkSendTx(cmd) => cmd.run().await?,
         Subcommands::ZkDepositTx(cmd) => cmd.run().await?,
+        Subcommands::ZkComputeAddress(cmd) => {
+            cmd.run()?;
+        }
+        Subcommands::ZkCreate2(cmd) => {
+            cmd.run()?;
+        }
+        Subcommands::ZkConfirmedTokens(cmd) => cmd.run().await?,
+        Subcommands::ZkSignTypedData(cmd) => cmd.run().await?,
+        Subcommands::ZkGasPrice(cmd) => cmd.run().await?,
+        Subcommands::ZkWithdraw(cmd) => cmd.run().await?,
+        Subcommands::ZkNetwork(cmd) => cmd.run()?,
+        Subcommands::ZkLogs(cmd) => cmd.run().await?,
+        Subcommands::ZkBlock(cmd) => cmd.run().await?,
+        Subcommands::ZkBytecodeByHash(cmd) => cmd.run().await?,
 
         Subcommands::Tx { tx_hash, field, raw, json, rpc } => {
             let config = Config::from(&rpc);
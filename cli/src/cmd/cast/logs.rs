@@ -120,7 +120,7 @@ where
 // First tries to parse the `sig_or_topic` as an event signature. If successful, `topics_or_args` is
 // parsed as indexed inputs and converted to topics. Otherwise, `sig_or_topic` is prepended to
 // `topics_or_args` and used as raw topics.
-fn build_filter(
+pub fn build_filter(
     from_block: Option<BlockNumber>,
     to_block: Option<BlockNumber>,
     address: Option<Address>,
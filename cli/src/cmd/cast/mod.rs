@@ -18,6 +18,15 @@ pub mod run;
 pub mod send;
 pub mod storage;
 pub mod wallet;
+pub mod zk_address;
+pub mod zk_block;
+pub mod zk_bytecode_by_hash;
 pub mod zk_deposit;
+pub mod zk_gas_price;
+pub mod zk_logs;
+pub mod zk_network;
 pub mod zk_send;
+pub mod zk_sign;
+pub mod zk_tokens;
 pub mod zk_utils;
+pub mod zk_withdraw;
@@ -0,0 +1,223 @@
+//! cast zk-compute-address and zk-create2 subcommands
+//!
+//! zkSync derives contract addresses differently than the EVM: both `CREATE` and `CREATE2` are
+//! handled by the `ContractDeployer` system contract, which hashes a zkSync specific prefix
+//! together with the deployer and, for `CREATE2`, the *bytecode hash* instead of the init code.
+//! These subcommands compute the resulting addresses offline, without a node.
+
+use crate::{cmd::Cmd, utils::parse_u256};
+use cast::SimpleCast;
+use clap::Parser;
+use ethers::{
+    types::{Address, Bytes, H256, U256},
+    utils::keccak256,
+};
+use eyre::{Result, WrapErr};
+
+/// CLI arguments for `cast zk-compute-address`.
+#[derive(Debug, Clone, Parser)]
+pub struct ZkComputeAddressArgs {
+    /// Address of the contract deployer.
+    #[clap(long, short, value_name = "ADDRESS")]
+    deployer: Address,
+
+    /// The deployment nonce of the deployer.
+    ///
+    /// Note: this is the nonce tracked by the `NonceHolder` for deployments, which differs from
+    /// the deployer's transaction nonce.
+    #[clap(long, short, value_parser = parse_u256, value_name = "NONCE")]
+    nonce: U256,
+
+    /// Print the computed address as JSON.
+    #[clap(long, short)]
+    json: bool,
+}
+
+impl Cmd for ZkComputeAddressArgs {
+    type Output = Address;
+
+    fn run(self) -> Result<Self::Output> {
+        let address = compute_create_address(self.deployer, self.nonce);
+        print_address(address, self.json)?;
+        Ok(address)
+    }
+}
+
+/// CLI arguments for `cast zk-create2`.
+#[derive(Debug, Clone, Parser)]
+pub struct ZkCreate2Args {
+    /// Address of the contract deployer.
+    #[clap(long, short, value_name = "ADDRESS")]
+    deployer: Address,
+
+    /// The 32 byte salt used for the deployment.
+    #[clap(long, short, value_parser = parse_salt, value_name = "HEX")]
+    salt: H256,
+
+    /// The zkSync bytecode hash of the contract to be deployed.
+    #[clap(long, short, value_parser = parse_bytecode_hash, value_name = "HASH")]
+    bytecode_hash: H256,
+
+    /// ABI encoded constructor arguments of the contract to be deployed.
+    #[clap(long, short, value_name = "HEX", default_value = "0x")]
+    constructor_input: Bytes,
+
+    /// Print the computed address as JSON.
+    #[clap(long, short)]
+    json: bool,
+}
+
+impl Cmd for ZkCreate2Args {
+    type Output = Address;
+
+    fn run(self) -> Result<Self::Output> {
+        let address = compute_create2_address(
+            self.deployer,
+            self.salt,
+            self.bytecode_hash,
+            &self.constructor_input,
+        );
+        print_address(address, self.json)?;
+        Ok(address)
+    }
+}
+
+/// Computes the address of a contract deployed with `CREATE` on zkSync.
+///
+/// `keccak256(keccak256("zksyncCreate") ++ pad32(sender) ++ pad32(nonce))[12..]`
+pub fn compute_create_address(sender: Address, nonce: U256) -> Address {
+    let mut nonce_bytes = [0u8; 32];
+    nonce.to_big_endian(&mut nonce_bytes);
+
+    let mut preimage = Vec::with_capacity(32 * 3);
+    preimage.extend_from_slice(&keccak256(b"zksyncCreate"));
+    preimage.extend_from_slice(H256::from(sender).as_bytes());
+    preimage.extend_from_slice(&nonce_bytes);
+
+    Address::from_slice(&keccak256(preimage)[12..])
+}
+
+/// Computes the address of a contract deployed with `CREATE2` on zkSync.
+///
+/// `keccak256(keccak256("zksyncCreate2") ++ pad32(sender) ++ salt ++ bytecodeHash ++
+/// keccak256(constructorInput))[12..]`
+pub fn compute_create2_address(
+    sender: Address,
+    salt: H256,
+    bytecode_hash: H256,
+    constructor_input: &[u8],
+) -> Address {
+    let mut preimage = Vec::with_capacity(32 * 5);
+    preimage.extend_from_slice(&keccak256(b"zksyncCreate2"));
+    preimage.extend_from_slice(H256::from(sender).as_bytes());
+    preimage.extend_from_slice(salt.as_bytes());
+    preimage.extend_from_slice(bytecode_hash.as_bytes());
+    preimage.extend_from_slice(&keccak256(constructor_input));
+
+    Address::from_slice(&keccak256(preimage)[12..])
+}
+
+fn print_address(address: Address, json: bool) -> Result<()> {
+    let address = SimpleCast::to_checksum_address(&address);
+    if json {
+        println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "address": address }))?);
+    } else {
+        println!("Computed Address: {address}");
+    }
+    Ok(())
+}
+
+/// Parses a hex string that must be exactly 32 bytes long
+fn parse_bytes32(s: &str, name: &str) -> Result<H256> {
+    let bytes = hex::decode(s.strip_prefix("0x").unwrap_or(s))
+        .wrap_err_with(|| format!("invalid {name} hex provided"))?;
+    if bytes.len() != 32 {
+        eyre::bail!("{name} should be 32 bytes long, got {} bytes", bytes.len());
+    }
+    Ok(H256::from_slice(&bytes))
+}
+
+fn parse_salt(s: &str) -> Result<H256> {
+    parse_bytes32(s, "salt")
+}
+
+/// Parses a zkSync bytecode hash.
+///
+/// The hash is versioned: the first byte is the version (`1`), the second byte must be zero and
+/// the next two bytes are the bytecode length in 32 byte words, which must be odd.
+fn parse_bytecode_hash(s: &str) -> Result<H256> {
+    let hash = parse_bytes32(s, "bytecode hash")?;
+    let bytes = hash.as_bytes();
+    if bytes[0] != 1 || bytes[1] != 0 {
+        eyre::bail!("invalid bytecode hash: expected a version 1 zkSync bytecode hash (0x0100...)");
+    }
+    if u16::from_be_bytes([bytes[2], bytes[3]]) % 2 == 0 {
+        eyre::bail!("invalid bytecode hash: the bytecode length in words must be odd");
+    }
+    Ok(hash)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::str::FromStr;
+
+    const DEPLOYER: &str = "0x36615Cf349d7F6344891B1e7CA7C72883F5dc049";
+    const BYTECODE_HASH: &str =
+        "0x010001cb6a6e8d5f6829522f19fa9568660e0a9cd53b2e8be4deb0a679452e41";
+
+    #[test]
+    fn can_compute_zk_create_address() {
+        let deployer = Address::from_str(DEPLOYER).unwrap();
+        assert_eq!(
+            compute_create_address(deployer, 1.into()),
+            Address::from_str("0x4B5DF730c2e6b28E17013A1485E5d9BC41Efe021").unwrap()
+        );
+        assert_eq!(
+            compute_create_address(deployer, 0.into()),
+            Address::from_str("0x111c3e89ce80e62ee88318c2804920d4c96f92bb").unwrap()
+        );
+    }
+
+    #[test]
+    fn can_compute_zk_create2_address() {
+        let args = ZkCreate2Args::parse_from([
+            "foundry-cli",
+            "--deployer",
+            DEPLOYER,
+            "--salt",
+            "0x0000000000000000000000000000000000000000000000000000000000000001",
+            "--bytecode-hash",
+            BYTECODE_HASH,
+            "--constructor-input",
+            "0x01",
+        ]);
+        assert_eq!(
+            args.clone().run().unwrap(),
+            Address::from_str("0x78ee9dea03a39f5cc04c80a575517ff5de02ec4c").unwrap()
+        );
+
+        let args = ZkCreate2Args { constructor_input: Bytes::default(), ..args };
+        assert_eq!(
+            args.run().unwrap(),
+            Address::from_str("0x73a1d763e16ad377b328cc6593bd0d5e524b6a0f").unwrap()
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_create2_inputs() {
+        assert!(parse_salt("0x01").is_err());
+        assert!(parse_salt("0xzz").is_err());
+        assert!(parse_bytecode_hash(BYTECODE_HASH).is_ok());
+        // keccak style hash without the zkSync version prefix
+        assert!(parse_bytecode_hash(
+            "0xc5d2460186f7233c927e7db2dcc703c0e500b653ca82273b7bfad8045d85a470"
+        )
+        .is_err());
+        // even length in words
+        assert!(parse_bytecode_hash(
+            "0x010002cb6a6e8d5f6829522f19fa9568660e0a9cd53b2e8be4deb0a679452e41"
+        )
+        .is_err());
+    }
+}
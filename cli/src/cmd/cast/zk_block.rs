@@ -0,0 +1,223 @@
+//! cast zk-block subcommand
+//!
+//! `eth_getBlockByNumber` returns EVM shaped transactions, which lack the fields of zkSync's
+//! EIP-712 (`0x71`) transactions. With `--full`, `zk-block` completes them with the fields of the
+//! same transactions as returned by `zks_getRawBlockTransactions`.
+
+use crate::{opts::RpcOpts, utils};
+use clap::Parser;
+use ethers::types::{BlockId, BlockNumber};
+use eyre::{Result, WrapErr};
+use foundry_config::Config;
+use serde_json::Value;
+
+/// CLI arguments for `cast zk-block`.
+#[derive(Debug, Clone, Parser)]
+pub struct ZkBlockArgs {
+    /// The block height or hash to query at.
+    ///
+    /// Can also be the tags earliest, finalized, safe, latest, or pending.
+    #[clap(value_name = "BLOCK")]
+    block: Option<BlockId>,
+
+    /// Include the full zk transaction objects.
+    #[clap(long)]
+    full: bool,
+
+    /// Print the block as JSON.
+    #[clap(long, short, help_heading = "Display options")]
+    json: bool,
+
+    #[clap(flatten)]
+    rpc: RpcOpts,
+}
+
+impl ZkBlockArgs {
+    pub async fn run(self) -> Result<()> {
+        let ZkBlockArgs { block, full, json, rpc } = self;
+        let config = Config::from(&rpc);
+        let provider = utils::get_provider(&config)?;
+
+        let mut block: Value = match block.unwrap_or(BlockId::Number(BlockNumber::Latest)) {
+            BlockId::Number(number) => {
+                provider.request("eth_getBlockByNumber", (number, full)).await
+            }
+            BlockId::Hash(hash) => provider.request("eth_getBlockByHash", (hash, full)).await,
+        }
+        .wrap_err("Failed to fetch the block")?;
+        if block.is_null() {
+            eyre::bail!("Block not found")
+        }
+
+        if full {
+            let number = block["number"]
+                .as_str()
+                .and_then(|n| u64::from_str_radix(n.trim_start_matches("0x"), 16).ok());
+            match number {
+                Some(number) => {
+                    let raw: Vec<Value> = provider
+                        .request("zks_getRawBlockTransactions", [number])
+                        .await
+                        .wrap_err("Failed to fetch the raw transactions, is this a zkSync node?")?;
+                    add_zk_fields(&mut block, &raw)?;
+                }
+                // the transactions of a pending block are not sealed into a block yet
+                None => eprintln!("The block is pending, its transactions lack the zk fields"),
+            }
+        }
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&block)?);
+        } else {
+            print_block(&block);
+        }
+        Ok(())
+    }
+}
+
+/// Completes the transactions of the block with the zk fields of the raw transactions, which are
+/// in the same order
+fn add_zk_fields(block: &mut Value, raw_transactions: &[Value]) -> Result<()> {
+    let Some(transactions) = block["transactions"].as_array_mut() else { return Ok(()) };
+    if transactions.len() != raw_transactions.len() {
+        eyre::bail!(
+            "The block has {} transactions, but {} raw transactions were returned",
+            transactions.len(),
+            raw_transactions.len()
+        )
+    }
+    for (tx, raw) in transactions.iter_mut().zip(raw_transactions) {
+        let Some(tx) = tx.as_object_mut() else { continue };
+        // L1 -> L2 priority transactions are not paid by the initiator and have no paymaster
+        let l2 = &raw["common_data"]["L2"];
+        if !l2.is_null() {
+            tx.insert("gasPerPubdata".to_string(), l2["fee"]["gas_per_pubdata_limit"].clone());
+            tx.insert("paymaster".to_string(), l2["paymasterParams"]["paymaster"].clone());
+            tx.insert(
+                "paymasterInput".to_string(),
+                to_hex(&l2["paymasterParams"]["paymasterInput"]),
+            );
+        }
+        let factory_deps = raw["execute"]["factoryDeps"]
+            .as_array()
+            .map(|deps| deps.iter().map(to_hex).collect::<Vec<_>>())
+            .unwrap_or_default();
+        tx.insert("factoryDeps".to_string(), factory_deps.into());
+    }
+    Ok(())
+}
+
+/// Converts bytes serialized as an array of numbers into a hex string
+fn to_hex(bytes: &Value) -> Value {
+    match bytes {
+        Value::Array(bytes) => {
+            let bytes: Vec<u8> = bytes.iter().filter_map(|b| b.as_u64()).map(|b| b as u8).collect();
+            format!("0x{}", hex::encode(bytes)).into()
+        }
+        Value::Null => "0x".into(),
+        other => other.clone(),
+    }
+}
+
+fn print_block(block: &Value) {
+    let Some(fields) = block.as_object() else { return };
+    for (key, value) in fields.iter().filter(|(key, _)| key.as_str() != "transactions") {
+        println!("{key:<24}{}", display(value));
+    }
+    let transactions = block["transactions"].as_array().cloned().unwrap_or_default();
+    println!("{:<24}{}", "transactions", transactions.len());
+    for tx in transactions {
+        let Value::Object(tx) = tx else {
+            // only the hashes are returned without `--full`
+            println!("  {}", display(&tx));
+            continue
+        };
+        println!();
+        for (key, value) in tx {
+            println!("  {key:<22}{}", display(&value));
+        }
+    }
+}
+
+fn display(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Null => "null".to_string(),
+        Value::Array(values) if values.iter().all(Value::is_string) => {
+            values.iter().map(display).collect::<Vec<_>>().join(", ")
+        }
+        other => other.to_string(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn can_add_zk_fields_from_recorded_response() {
+        // `eth_getBlockByNumber` and `zks_getRawBlockTransactions` responses of zkSync Era
+        // testnet, shortened
+        let mut block = json!({
+            "number": "0x14",
+            "hash": "0x1ac7ff41b98ec1e0ed2a1300e6e0066d1ea7f927c4ef9ace4e6f2cf55449ed46",
+            "transactions": [{
+                "hash": "0x5d2b19dad39c2a1e2f8ad2fe2feff6e35e472f24e1c3a7d872a73a0a3fb5c1ab",
+                "type": "0x71",
+                "from": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049",
+                "to": "0x0000000000000000000000000000000000008006",
+                "input": "0x9c4d535b"
+            }]
+        });
+        let raw: Vec<Value> = serde_json::from_value(json!([{
+            "common_data": { "L2": {
+                "nonce": 3,
+                "fee": {
+                    "gas_limit": "0x4c4b40",
+                    "max_fee_per_gas": "0xee6b280",
+                    "max_priority_fee_per_gas": "0x0",
+                    "gas_per_pubdata_limit": "0xc350"
+                },
+                "initiatorAddress": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049",
+                "transactionType": "EIP712Transaction",
+                "paymasterParams": {
+                    "paymaster": "0xa61464658afeaf65cccaafd3a512b69a83b77618",
+                    "paymasterInput": [140, 90, 52, 69]
+                }
+            }},
+            "execute": {
+                "contractAddress": "0x0000000000000000000000000000000000008006",
+                "calldata": "0x9c4d535b",
+                "value": "0x0",
+                "factoryDeps": [[0, 0, 0, 1]]
+            },
+            "received_timestamp_ms": 1689345748451u64
+        }]))
+        .unwrap();
+
+        add_zk_fields(&mut block, &raw).unwrap();
+        let tx = &block["transactions"][0];
+        assert_eq!(tx["type"], "0x71");
+        assert_eq!(tx["gasPerPubdata"], "0xc350");
+        assert_eq!(tx["paymaster"], "0xa61464658afeaf65cccaafd3a512b69a83b77618");
+        assert_eq!(tx["paymasterInput"], "0x8c5a3445");
+        assert_eq!(tx["factoryDeps"], json!(["0x00000001"]));
+
+        // the raw transactions must match the block's
+        assert!(add_zk_fields(&mut block, &[]).is_err());
+    }
+
+    #[test]
+    fn leaves_priority_transactions_without_paymaster() {
+        let mut block = json!({ "transactions": [{ "hash": "0x01", "type": "0xff" }] });
+        let raw = vec![json!({
+            "common_data": { "L1": { "serialId": 7 } },
+            "execute": { "factoryDeps": null }
+        })];
+        add_zk_fields(&mut block, &raw).unwrap();
+        let tx = &block["transactions"][0];
+        assert!(tx.get("paymaster").is_none());
+        assert_eq!(tx["factoryDeps"], json!([]));
+    }
+}
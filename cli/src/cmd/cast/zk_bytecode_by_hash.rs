@@ -0,0 +1,88 @@
+//! cast zk-bytecode-by-hash subcommand
+//!
+//! zkSync identifies deployed bytecode by its bytecode hash rather than by an address. Nodes that
+//! support `zks_getBytecodeByHash` return the bytecode of every hash that was published as a
+//! factory dependency.
+
+use crate::{cmd::forge::zksolc::zk_bytecode_hash, opts::RpcOpts, utils};
+use clap::Parser;
+use ethers::types::H256;
+use eyre::{Result, WrapErr};
+use foundry_config::Config;
+
+/// CLI arguments for `cast zk-bytecode-by-hash`.
+#[derive(Debug, Clone, Parser)]
+pub struct ZkBytecodeByHashArgs {
+    /// The zkSync bytecode hash to fetch the bytecode of.
+    #[clap(value_name = "HASH")]
+    hash: H256,
+
+    #[clap(flatten)]
+    rpc: RpcOpts,
+}
+
+impl ZkBytecodeByHashArgs {
+    pub async fn run(self) -> Result<()> {
+        let ZkBytecodeByHashArgs { hash, rpc } = self;
+        let config = Config::from(&rpc);
+        let provider = utils::get_provider(&config)?;
+
+        let bytecode: Option<Vec<u8>> = provider
+            .request("zks_getBytecodeByHash", [hash])
+            .await
+            .wrap_err("Failed to fetch the bytecode, is this a zkSync node?")?;
+        let bytecode = checked_bytecode(hash, bytecode)?;
+        println!("0x{}", hex::encode(bytecode));
+        Ok(())
+    }
+}
+
+/// Returns the bytecode the node returned for the hash, failing if it knows no bytecode with that
+/// hash or returned bytecode with a different hash
+fn checked_bytecode(hash: H256, bytecode: Option<Vec<u8>>) -> Result<Vec<u8>> {
+    let bytecode =
+        bytecode.ok_or_else(|| eyre::eyre!("The node knows no bytecode with hash {hash:?}"))?;
+    let actual = H256(zk_bytecode_hash(&bytecode));
+    if actual != hash {
+        eyre::bail!("The node returned bytecode with hash {actual:?} instead of {hash:?}")
+    }
+    Ok(bytecode)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::Value;
+    use std::str::FromStr;
+
+    const HASH: &str = "0x0100000365707b3cd3fdbcf77323b32be0ac73ac89d0c142ac4b268c6842af92";
+
+    #[test]
+    fn can_check_recorded_bytecode() {
+        // `zks_getBytecodeByHash` response, the bytecode is serialized as an array of bytes
+        let response: Value = serde_json::from_str(
+            r#"{"jsonrpc":"2.0","id":1,"result":[0,0,0,128,3,0,0,57,0,0,0,64,0,48,4,63,0,0,0,1,0,32,1,144,0,0,0,21,0,0,193,61,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,2,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0,0]}"#,
+        )
+        .unwrap();
+        let bytecode: Option<Vec<u8>> = serde_json::from_value(response["result"].clone()).unwrap();
+        let hash = H256::from_str(HASH).unwrap();
+
+        let bytecode = checked_bytecode(hash, bytecode).unwrap();
+        assert_eq!(bytecode.len(), 3 * 32);
+        assert_eq!(H256(zk_bytecode_hash(&bytecode)), hash);
+
+        let err = checked_bytecode(H256::zero(), Some(bytecode)).unwrap_err();
+        assert!(err.to_string().contains("instead of"));
+    }
+
+    #[test]
+    fn unknown_hash_is_an_error() {
+        let hash = H256::from_str(HASH).unwrap();
+        let err = checked_bytecode(hash, None).unwrap_err();
+        assert!(err.to_string().contains("knows no bytecode"));
+
+        let args = ZkBytecodeByHashArgs::parse_from(["foundry-cli", HASH]);
+        assert_eq!(args.hash, hash);
+        assert!(ZkBytecodeByHashArgs::try_parse_from(["foundry-cli", "0x01"]).is_err());
+    }
+}
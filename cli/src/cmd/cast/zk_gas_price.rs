@@ -0,0 +1,97 @@
+//! cast zk-gas-price subcommand
+//!
+//! zkSync charges L2 gas for execution and, for every byte of pubdata published to L1, an amount
+//! of L2 gas that depends on the L1 gas price. `zk-gas-price` reports both sides of that price.
+
+use crate::{opts::RpcOpts, utils};
+use clap::Parser;
+use ethers::{providers::Middleware, types::U256};
+use eyre::{Result, WrapErr};
+use foundry_config::Config;
+use serde::Serialize;
+
+/// The L1 gas spent on publishing a single byte of pubdata
+pub const L1_GAS_PER_PUBDATA_BYTE: u64 = 17;
+
+/// CLI arguments for `cast zk-gas-price`.
+#[derive(Debug, Clone, Parser)]
+pub struct ZkGasPriceArgs {
+    /// Print the gas prices as JSON.
+    #[clap(long, short)]
+    json: bool,
+
+    #[clap(flatten)]
+    rpc: RpcOpts,
+}
+
+/// The gas prices of a zkSync chain, in wei
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkGasPrice {
+    pub l2_gas_price: U256,
+    /// The L2 gas charged per byte of pubdata
+    pub gas_per_pubdata: U256,
+    pub l1_gas_price: U256,
+}
+
+impl ZkGasPrice {
+    /// Derives the gas per pubdata byte from the L1 and L2 gas prices, rounding up like the
+    /// bootloader does
+    pub fn new(l2_gas_price: U256, l1_gas_price: U256) -> Self {
+        let gas_per_pubdata = if l2_gas_price.is_zero() {
+            U256::zero()
+        } else {
+            let pubdata_price = l1_gas_price * L1_GAS_PER_PUBDATA_BYTE;
+            (pubdata_price + l2_gas_price - 1) / l2_gas_price
+        };
+        Self { l2_gas_price, gas_per_pubdata, l1_gas_price }
+    }
+}
+
+impl ZkGasPriceArgs {
+    pub async fn run(self) -> Result<()> {
+        let config = Config::from(&self.rpc);
+        let provider = utils::get_provider(&config)?;
+
+        let l2_gas_price = provider.get_gas_price().await.wrap_err("Failed to fetch gas price")?;
+        let l1_gas_price: U256 = provider
+            .request("zks_getL1GasPrice", ())
+            .await
+            .wrap_err("Failed to fetch the L1 gas price, is this a zkSync node?")?;
+        let prices = ZkGasPrice::new(l2_gas_price, l1_gas_price);
+
+        if self.json {
+            println!("{}", serde_json::to_string_pretty(&prices)?);
+        } else {
+            println!("L2 gas price:    {}", prices.l2_gas_price);
+            println!("Gas per pubdata: {}", prices.gas_per_pubdata);
+            println!("L1 gas price:    {}", prices.l1_gas_price);
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_derive_gas_prices_from_recorded_responses() {
+        // `eth_gasPrice` and `zks_getL1GasPrice` responses of zkSync Era testnet
+        let l2_gas_price: U256 = serde_json::from_str(r#""0xee6b280""#).unwrap();
+        let l1_gas_price: U256 = serde_json::from_str(r#""0x3b9aca0a""#).unwrap();
+
+        let prices = ZkGasPrice::new(l2_gas_price, l1_gas_price);
+        assert_eq!(prices.l2_gas_price, U256::from(250_000_000u64));
+        assert_eq!(prices.l1_gas_price, U256::from(1_000_000_010u64));
+        // 17 * 1_000_000_010 / 250_000_000 = 68.00000068, rounded up
+        assert_eq!(prices.gas_per_pubdata, U256::from(69u64));
+
+        let json = serde_json::to_value(&prices).unwrap();
+        for field in ["l2GasPrice", "gasPerPubdata", "l1GasPrice"] {
+            assert!(json.get(field).is_some(), "missing {field}");
+        }
+
+        assert!(ZkGasPrice::new(U256::zero(), l1_gas_price).gas_per_pubdata.is_zero());
+    }
+}
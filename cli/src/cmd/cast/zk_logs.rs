@@ -0,0 +1,149 @@
+//! cast zk-logs subcommand
+//!
+//! Queries logs bounded by L1 batches instead of L2 blocks. The batches are translated to the
+//! range of L2 blocks they contain via `zks_getL1BatchBlockRange`.
+
+use super::logs::build_filter;
+use crate::{opts::RpcOpts, utils};
+use cast::Cast;
+use clap::Parser;
+use ethers::types::{Address, BlockNumber, U64};
+use eyre::{Result, WrapErr};
+use foundry_config::Config;
+
+/// CLI arguments for `cast zk-logs`.
+#[derive(Debug, Parser)]
+pub struct ZkLogsArgs {
+    /// The L1 batch to start the query at.
+    #[clap(long, value_name = "BATCH")]
+    from_batch: u64,
+
+    /// The L1 batch to stop the query at.
+    ///
+    /// If the batch isn't sealed yet, the query includes all blocks up to the latest one.
+    /// Defaults to the `--from-batch`.
+    #[clap(long, value_name = "BATCH")]
+    to_batch: Option<u64>,
+
+    /// The contract address to filter on.
+    #[clap(long)]
+    address: Option<Address>,
+
+    /// The signature of the event to filter logs by which will be converted to the first topic or
+    /// a topic to filter on.
+    #[clap(value_name = "SIG_OR_TOPIC")]
+    sig_or_topic: Option<String>,
+
+    /// If used with a signature, the indexed fields of the event to filter by. Otherwise, the
+    /// remaining topics of the filter.
+    #[clap(value_name = "TOPICS_OR_ARGS")]
+    topics_or_args: Vec<String>,
+
+    /// Print the logs as JSON.
+    #[clap(long, short, help_heading = "Display options")]
+    json: bool,
+
+    #[clap(flatten)]
+    rpc: RpcOpts,
+}
+
+impl ZkLogsArgs {
+    pub async fn run(self) -> Result<()> {
+        let ZkLogsArgs { from_batch, to_batch, address, sig_or_topic, topics_or_args, json, rpc } =
+            self;
+        let to_batch = to_batch.unwrap_or(from_batch);
+        if from_batch > to_batch {
+            eyre::bail!("--from-batch {from_batch} is after --to-batch {to_batch}");
+        }
+
+        let config = Config::from(&rpc);
+        let provider = utils::get_provider(&config)?;
+
+        let from_range: Option<(U64, U64)> = provider
+            .request("zks_getL1BatchBlockRange", [from_batch])
+            .await
+            .wrap_err("Failed to fetch the blocks of the L1 batch, is this a zkSync node?")?;
+        let to_range: Option<(U64, U64)> = provider
+            .request("zks_getL1BatchBlockRange", [to_batch])
+            .await
+            .wrap_err("Failed to fetch the blocks of the L1 batch")?;
+        if to_range.is_none() {
+            eprintln!("L1 batch {to_batch} is not sealed yet, querying up to the latest block");
+        }
+        let (from_block, to_block) = batch_block_range(from_batch, from_range, to_range)?;
+
+        let filter =
+            build_filter(Some(from_block), Some(to_block), address, sig_or_topic, topics_or_args)?;
+        let logs = Cast::new(&provider).filter_logs(filter, json).await?;
+        println!("{logs}");
+        Ok(())
+    }
+}
+
+/// Returns the block range spanning the L1 batches with the given block ranges.
+///
+/// The first batch must be sealed, an unsealed last batch extends the range to the latest block.
+fn batch_block_range(
+    from_batch: u64,
+    from_range: Option<(U64, U64)>,
+    to_range: Option<(U64, U64)>,
+) -> Result<(BlockNumber, BlockNumber)> {
+    let (from_block, _) = from_range.ok_or_else(|| {
+        eyre::eyre!("L1 batch {from_batch} is not sealed yet, there are no blocks to query")
+    })?;
+    let to_block = match to_range {
+        Some((_, to_block)) => BlockNumber::Number(to_block),
+        None => BlockNumber::Latest,
+    };
+    Ok((BlockNumber::Number(from_block), to_block))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::providers::Middleware;
+    use std::env;
+
+    #[test]
+    fn can_translate_batches_to_blocks() {
+        let range = |from: u64, to: u64| Some((U64::from(from), U64::from(to)));
+
+        assert_eq!(
+            batch_block_range(3, range(10, 14), range(20, 25)).unwrap(),
+            (BlockNumber::Number(10.into()), BlockNumber::Number(25.into()))
+        );
+        assert_eq!(
+            batch_block_range(3, range(10, 14), None).unwrap(),
+            (BlockNumber::Number(10.into()), BlockNumber::Latest)
+        );
+        assert!(batch_block_range(3, None, None).is_err());
+
+        let args = ZkLogsArgs::parse_from(["foundry-cli", "--from-batch", "3", "--to-batch", "4"]);
+        assert_eq!((args.from_batch, args.to_batch), (3, Some(4)));
+        assert!(ZkLogsArgs::try_parse_from(["foundry-cli"]).is_err());
+    }
+
+    #[tokio::test]
+    async fn only_logs_of_the_batch_are_returned() {
+        let Ok(l2_url) = env::var("L2_RPC_URL") else {
+            // TODO: this test requires a running L2 to pass.
+            return
+        };
+        let config = Config { eth_rpc_url: Some(l2_url), ..Default::default() };
+        let provider = utils::get_provider(&config).unwrap();
+
+        let batch = 1u64;
+        let range: Option<(U64, U64)> =
+            provider.request("zks_getL1BatchBlockRange", [batch]).await.unwrap();
+        let (from_block, to_block) = batch_block_range(batch, range, range).unwrap();
+        let (start, end) = range.unwrap();
+
+        let filter = build_filter(Some(from_block), Some(to_block), None, None, vec![]).unwrap();
+        let logs = provider.get_logs(&filter).await.unwrap();
+        assert!(!logs.is_empty());
+        for log in logs {
+            let block = log.block_number.unwrap();
+            assert!(start <= block && block <= end, "log of block {block} outside the batch");
+        }
+    }
+}
@@ -0,0 +1,128 @@
+//! cast zk-network subcommand
+//!
+//! Identifies a zkSync-stack chain from its chain id and prints the canonical endpoints of the
+//! network, e.g. to fill in `--rpc-url`, `--l2-url` and `--chain` for the other zk commands.
+
+use clap::Parser;
+use eyre::Result;
+use serde::Serialize;
+
+/// The zkSync-stack networks `zk-network` knows about
+pub const KNOWN_ZK_NETWORKS: &[ZkNetwork] = &[
+    ZkNetwork {
+        name: "zkSync Era Local",
+        chain_id: 270,
+        l2_rpc_url: "http://localhost:3050",
+        l1_rpc_url: "http://localhost:8545",
+        explorer_url: None,
+    },
+    ZkNetwork {
+        name: "zkSync Era Goerli Testnet",
+        chain_id: 280,
+        l2_rpc_url: "https://testnet.era.zksync.dev",
+        l1_rpc_url: "https://rpc.ankr.com/eth_goerli",
+        explorer_url: Some("https://goerli.explorer.zksync.io"),
+    },
+    ZkNetwork {
+        name: "zkSync Era Sepolia Testnet",
+        chain_id: 300,
+        l2_rpc_url: "https://sepolia.era.zksync.dev",
+        l1_rpc_url: "https://rpc.sepolia.org",
+        explorer_url: Some("https://sepolia.explorer.zksync.io"),
+    },
+    ZkNetwork {
+        name: "zkSync Era Mainnet",
+        chain_id: 324,
+        l2_rpc_url: "https://mainnet.era.zksync.io",
+        l1_rpc_url: "https://cloudflare-eth.com",
+        explorer_url: Some("https://explorer.zksync.io"),
+    },
+];
+
+/// CLI arguments for `cast zk-network`.
+#[derive(Debug, Clone, Parser)]
+pub struct ZkNetworkArgs {
+    /// The chain id to look up.
+    #[clap(long, value_name = "CHAIN_ID")]
+    chain_id: u64,
+
+    /// Print the network as JSON.
+    #[clap(long, short)]
+    json: bool,
+}
+
+/// A zkSync-stack network and its canonical endpoints
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkNetwork {
+    pub name: &'static str,
+    pub chain_id: u64,
+    pub l2_rpc_url: &'static str,
+    /// The RPC of the L1 the network settles on
+    pub l1_rpc_url: &'static str,
+    pub explorer_url: Option<&'static str>,
+}
+
+impl ZkNetwork {
+    /// Returns the known network with the given chain id
+    pub fn from_chain_id(chain_id: u64) -> Option<Self> {
+        KNOWN_ZK_NETWORKS.iter().find(|network| network.chain_id == chain_id).copied()
+    }
+}
+
+impl ZkNetworkArgs {
+    pub fn run(self) -> Result<()> {
+        let ZkNetworkArgs { chain_id, json } = self;
+        match ZkNetwork::from_chain_id(chain_id) {
+            Some(network) if json => println!("{}", serde_json::to_string_pretty(&network)?),
+            Some(network) => {
+                println!("Network:      {}", network.name);
+                println!("Chain id:     {}", network.chain_id);
+                println!("L2 RPC URL:   {}", network.l2_rpc_url);
+                println!("L1 RPC URL:   {}", network.l1_rpc_url);
+                println!("Explorer URL: {}", network.explorer_url.unwrap_or("-"));
+            }
+            None if json => println!(
+                "{}",
+                serde_json::to_string_pretty(&serde_json::json!({
+                    "chainId": chain_id,
+                    "known": false,
+                }))?
+            ),
+            None => {
+                println!("Chain id:     {chain_id}");
+                println!("Not a known zkSync-stack network, no endpoints available.");
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_resolve_known_networks() {
+        let network = ZkNetwork::from_chain_id(324).unwrap();
+        assert_eq!(network.name, "zkSync Era Mainnet");
+        assert_eq!(network.l2_rpc_url, "https://mainnet.era.zksync.io");
+        assert_eq!(network.explorer_url, Some("https://explorer.zksync.io"));
+
+        let json = serde_json::to_value(network).unwrap();
+        for field in ["name", "chainId", "l2RpcUrl", "l1RpcUrl", "explorerUrl"] {
+            assert!(json.get(field).is_some(), "missing {field}");
+        }
+
+        assert!(ZkNetwork::from_chain_id(270).unwrap().explorer_url.is_none());
+    }
+
+    #[test]
+    fn unknown_chain_id_is_reported() {
+        assert!(ZkNetwork::from_chain_id(1).is_none());
+
+        let args = ZkNetworkArgs::parse_from(["foundry-cli", "--chain-id", "123456", "--json"]);
+        assert!(args.run().is_ok());
+        assert!(ZkNetworkArgs::try_parse_from(["foundry-cli"]).is_err());
+    }
+}
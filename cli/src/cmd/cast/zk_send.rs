@@ -55,7 +55,10 @@ use zksync_web3_rs::{
     ZKSWallet,
 };
 
-use super::zk_utils::{get_chain, get_private_key, get_rpc_url};
+use super::{
+    zk_tokens::BaseToken,
+    zk_utils::{get_chain, get_private_key, get_rpc_url},
+};
 
 /// CLI arguments for the `cast zk-send` subcommand.
 ///
@@ -144,6 +147,13 @@ impl ZkSendTxArgs {
         let config = Config::from(&self.eth);
         let chain = get_chain(config.chain_id)?;
         let provider = Provider::try_from(rpc_url)?;
+        let base_token = BaseToken::fetch(&provider).await?;
+        if !base_token.is_eth() {
+            eprintln!(
+                "Warning: fees on this chain are paid in {} ({:?}), not ETH",
+                base_token.symbol, base_token.l1_address
+            );
+        }
         let to_address = self.get_to_address();
         let wallet = LocalWallet::from_str(&format!("{private_key:?}"))?.with_chain_id(chain);
         let zk_wallet = ZKSWallet::new(wallet, None, Some(provider), None);
@@ -162,7 +172,7 @@ impl ZkSendTxArgs {
                         .await?
                         .await?
                         .ok_or(eyre::eyre!("Error getting the receipt for withdraw"))?;
-                    self.print_receipt(&tx_rcpt);
+                    self.print_receipt(&tx_rcpt, &base_token);
                 }
                 Err(e) => eyre::bail!("error wallet: {e:?}"),
             };
@@ -186,7 +196,7 @@ impl ZkSendTxArgs {
                         .await?
                         .ok_or(eyre::eyre!("Error getting the receipt for transaction"))?;
 
-                    self.print_receipt(&rcpt);
+                    self.print_receipt(&rcpt, &base_token);
                 }
                 Err(e) => eyre::bail!("error wallet: {e:?}"),
             };
@@ -198,13 +208,14 @@ impl ZkSendTxArgs {
     /// Prints the receipt of the transaction.
     ///
     /// This function extracts the transaction hash, gas used, effective gas price, and block number
-    /// from the receipt and prints them. It also prints the address of the deployed contract, if
-    /// any.
+    /// from the receipt and prints them, along with the fee paid in the chain's base token. It also
+    /// prints the address of the deployed contract, if any.
     ///
     /// # Arguments
     ///
     /// * `rcpt` - A reference to the `TransactionReceipt`.
-    fn print_receipt(&self, rcpt: &TransactionReceipt) {
+    /// * `base_token` - The token fees are paid in on this chain.
+    fn print_receipt(&self, rcpt: &TransactionReceipt, base_token: &BaseToken) {
         let gas_used = rcpt.gas_used.expect("Error retrieving gas used");
         let gas_price = rcpt.effective_gas_price.expect("Error retrieving gas price");
         let block_number = rcpt.block_number.expect("Error retrieving block number");
@@ -213,6 +224,10 @@ impl ZkSendTxArgs {
         println!("Transaction Hash: {:#?}", rcpt.transaction_hash);
         println!("Gas used: {:#?}", gas_used);
         println!("Effective gas price: {:#?}", gas_price);
+        // zksync-web3-rs uses its own ethers version, so convert the fee through its decimal value
+        let fee = ethers::types::U256::from_dec_str(&(gas_used * gas_price).to_string())
+            .unwrap_or_default();
+        println!("Fee: {}", base_token.format_fee(fee));
         println!("Block Number: {:#?}", block_number);
         println!("+-------------------------------------------------+");
 
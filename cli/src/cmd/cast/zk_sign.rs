@@ -0,0 +1,158 @@
+//! cast zk-sign-typed-data subcommand
+//!
+//! Signs EIP-712 typed data, e.g. permits for account abstraction, under the domain zkSync uses
+//! for its own transactions: `name = "zkSync"`, `version = "2"` and the chain id of the network.
+
+use crate::opts::{ChainValueParser, Wallet};
+use clap::Parser;
+use ethers::{
+    signers::Signer,
+    types::{transaction::eip712::TypedData, U256},
+};
+use eyre::{Result, WrapErr};
+use foundry_config::Chain;
+use serde_json::Value;
+use std::path::PathBuf;
+
+/// The EIP-712 domain name of zkSync
+pub const ZKSYNC_DOMAIN_NAME: &str = "zkSync";
+
+/// The EIP-712 domain version of zkSync
+pub const ZKSYNC_DOMAIN_VERSION: &str = "2";
+
+/// CLI arguments for `cast zk-sign-typed-data`.
+#[derive(Debug, Clone, Parser)]
+pub struct ZkSignTypedDataArgs {
+    /// Path to a JSON file containing the typed data to sign.
+    ///
+    /// The `domain` may be omitted, it is set to the zkSync domain of the given chain. A `domain`
+    /// that is present must not conflict with it.
+    #[clap(value_name = "FILE")]
+    file: PathBuf,
+
+    /// The chain name or EIP-155 chain ID of the zkSync network the data is signed for.
+    #[clap(long, alias = "chain-id", env = "CHAIN", value_parser = ChainValueParser::default())]
+    chain: Chain,
+
+    #[clap(flatten)]
+    wallet: Wallet,
+}
+
+impl ZkSignTypedDataArgs {
+    pub async fn run(self) -> Result<()> {
+        let ZkSignTypedDataArgs { file, chain, wallet } = self;
+        let value: Value = foundry_common::fs::read_json_file(&file)?;
+        let typed_data = zk_typed_data(value, chain.id())
+            .wrap_err_with(|| format!("Invalid typed data in {}", file.display()))?;
+
+        let wallet = wallet.signer(chain.id()).await?;
+        let sig = wallet.sign_typed_data(&typed_data).await?;
+        println!("0x{sig}");
+        Ok(())
+    }
+}
+
+/// Parses the given typed data and sets its domain to the zkSync domain of the given chain.
+///
+/// Fails if the data is malformed, the primary type isn't defined, or the domain sets a name,
+/// version or chain id different from the zkSync one.
+pub fn zk_typed_data(mut value: Value, chain_id: u64) -> Result<TypedData> {
+    let obj = value.as_object_mut().ok_or_else(|| eyre::eyre!("expected a JSON object"))?;
+    obj.entry("domain").or_insert_with(|| Value::Object(Default::default()));
+    let mut typed_data: TypedData =
+        serde_json::from_value(value).wrap_err("expected `types`, `primaryType` and `message`")?;
+
+    if !typed_data.types.contains_key(&typed_data.primary_type) {
+        eyre::bail!("primary type `{}` is not defined in `types`", typed_data.primary_type);
+    }
+
+    let domain = &mut typed_data.domain;
+    match domain.name.as_deref() {
+        None | Some(ZKSYNC_DOMAIN_NAME) => {}
+        Some(name) => eyre::bail!("domain name must be `{ZKSYNC_DOMAIN_NAME}`, got `{name}`"),
+    }
+    match domain.version.as_deref() {
+        None | Some(ZKSYNC_DOMAIN_VERSION) => {}
+        Some(version) => {
+            eyre::bail!("domain version must be `{ZKSYNC_DOMAIN_VERSION}`, got `{version}`")
+        }
+    }
+    match domain.chain_id {
+        Some(id) if id != U256::from(chain_id) => {
+            eyre::bail!("domain chain id {id} doesn't match the chain id {chain_id}")
+        }
+        _ => {}
+    }
+
+    domain.name = Some(ZKSYNC_DOMAIN_NAME.to_string());
+    domain.version = Some(ZKSYNC_DOMAIN_VERSION.to_string());
+    domain.chain_id = Some(chain_id.into());
+    Ok(typed_data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ethers::{
+        signers::LocalWallet,
+        types::{transaction::eip712::Eip712, H256},
+    };
+    use serde_json::json;
+
+    fn permit() -> Value {
+        json!({
+            "types": {
+                "Permit": [
+                    { "name": "owner", "type": "address" },
+                    { "name": "spender", "type": "address" },
+                    { "name": "value", "type": "uint256" }
+                ]
+            },
+            "primaryType": "Permit",
+            "message": {
+                "owner": "0x36615Cf349d7F6344891B1e7CA7C72883F5dc049",
+                "spender": "0xa61464658AfeAf65CccaaFD3a512b69A83B77618",
+                "value": "1000"
+            }
+        })
+    }
+
+    #[tokio::test]
+    async fn signature_recovers_to_signer_under_zk_domain() {
+        let typed_data = zk_typed_data(permit(), 280).unwrap();
+        assert_eq!(typed_data.domain.name.as_deref(), Some("zkSync"));
+        assert_eq!(typed_data.domain.version.as_deref(), Some("2"));
+        assert_eq!(typed_data.domain.chain_id, Some(280u64.into()));
+
+        let wallet: LocalWallet =
+            "7726827caac94a7f9e1b160f7ea819f172f7b6f9d2a97f992c38edeab82d4110".parse().unwrap();
+        let sig = wallet.sign_typed_data(&typed_data).await.unwrap();
+
+        let hash = H256::from(typed_data.encode_eip712().unwrap());
+        assert_eq!(sig.recover(hash).unwrap(), wallet.address());
+
+        // the same data signed for another chain doesn't recover to the signer
+        let other = zk_typed_data(permit(), 324).unwrap();
+        let hash = H256::from(other.encode_eip712().unwrap());
+        assert_ne!(sig.recover(hash).unwrap(), wallet.address());
+    }
+
+    #[test]
+    fn rejects_invalid_typed_data() {
+        let mut data = permit();
+        data["domain"] = json!({ "name": "Ether Mail", "version": "2" });
+        assert!(zk_typed_data(data, 280).is_err());
+
+        let mut data = permit();
+        data["domain"] = json!({ "name": "zkSync", "version": "2", "chainId": 324 });
+        assert!(zk_typed_data(data.clone(), 280).is_err());
+        assert!(zk_typed_data(data, 324).is_ok());
+
+        let mut data = permit();
+        data["primaryType"] = json!("Transfer");
+        assert!(zk_typed_data(data, 280).is_err());
+
+        assert!(zk_typed_data(json!({ "primaryType": "Permit" }), 280).is_err());
+        assert!(zk_typed_data(json!([]), 280).is_err());
+    }
+}
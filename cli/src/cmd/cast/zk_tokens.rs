@@ -0,0 +1,318 @@
+//! cast zk-confirmed-tokens subcommand
+//!
+//! Lists the tokens bridged to zkSync via `zks_getConfirmedTokens`, and resolves the base token
+//! fees are paid in.
+
+use crate::{opts::RpcOpts, utils};
+use cast::SimpleCast;
+use clap::Parser;
+use ethers::{
+    types::{Address, H160, U256},
+    utils::format_units,
+};
+use eyre::{Result, WrapErr};
+use foundry_config::Config;
+use serde::{Deserialize, Serialize};
+use zksync_web3_rs::providers::{Http, Provider, ProviderError, RpcError};
+
+/// The maximum number of tokens `zks_getConfirmedTokens` returns per request.
+const MAX_PAGE_SIZE: u32 = u8::MAX as u32;
+
+/// The maximum number of confirmed tokens searched for the base token of a chain.
+const MAX_BASE_TOKEN_SEARCH: u32 = 40 * MAX_PAGE_SIZE;
+
+/// The JSON-RPC error code of calls to methods the node doesn't support.
+const METHOD_NOT_FOUND: i64 = -32601;
+
+/// CLI arguments for `cast zk-confirmed-tokens`.
+#[derive(Debug, Clone, Parser)]
+pub struct ZkConfirmedTokensArgs {
+    /// The token id to start listing from.
+    #[clap(long, default_value = "0", value_name = "ID")]
+    from: u32,
+
+    /// The maximum number of tokens to list.
+    ///
+    /// Requests are paginated, so this may exceed the node's per request limit of 255.
+    #[clap(
+        long,
+        default_value = "100",
+        value_name = "LIMIT",
+        value_parser = clap::value_parser!(u32).range(1..)
+    )]
+    limit: u32,
+
+    /// Print the tokens as JSON.
+    #[clap(long, short)]
+    json: bool,
+
+    #[clap(flatten)]
+    rpc: RpcOpts,
+}
+
+/// A token as returned by `zks_getConfirmedTokens`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkToken {
+    pub l1_address: Address,
+    pub l2_address: Address,
+    pub name: String,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl ZkConfirmedTokensArgs {
+    pub async fn run(self) -> Result<()> {
+        let ZkConfirmedTokensArgs { from, limit, json, rpc } = self;
+        if from.checked_add(limit).is_none() {
+            eyre::bail!("--from + --limit must not exceed {}", u32::MAX);
+        }
+
+        let config = Config::from(&rpc);
+        let provider = utils::get_provider(&config)?;
+
+        let mut tokens = Vec::new();
+        for (offset, page_size) in pages(from, limit) {
+            let page: Vec<ZkToken> = provider
+                .request("zks_getConfirmedTokens", (offset, page_size))
+                .await
+                .wrap_err("Failed to fetch confirmed tokens")?;
+            let is_last = page.len() < page_size as usize;
+            tokens.extend(page);
+            if is_last {
+                break
+            }
+        }
+
+        if json {
+            println!("{}", serde_json::to_string_pretty(&tokens)?);
+        } else {
+            for token in tokens {
+                println!(
+                    "{} ({}) decimals: {}\n  L1: {}\n  L2: {}",
+                    token.symbol,
+                    token.name,
+                    token.decimals,
+                    SimpleCast::to_checksum_address(&token.l1_address),
+                    SimpleCast::to_checksum_address(&token.l2_address)
+                );
+            }
+        }
+        Ok(())
+    }
+}
+
+/// The L1 address `zks_getBaseTokenL1Address` returns for chains paying fees in ETH
+pub const ETH_BASE_TOKEN_L1_ADDRESS: Address =
+    H160([0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 0, 1]);
+
+/// The token transaction fees are paid in on a zkSync chain
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaseToken {
+    pub l1_address: Address,
+    pub symbol: String,
+    pub decimals: u8,
+}
+
+impl BaseToken {
+    pub fn eth() -> Self {
+        Self { l1_address: ETH_BASE_TOKEN_L1_ADDRESS, symbol: "ETH".to_string(), decimals: 18 }
+    }
+
+    pub fn is_eth(&self) -> bool {
+        self.l1_address == ETH_BASE_TOKEN_L1_ADDRESS || self.l1_address.is_zero()
+    }
+
+    /// Fetches the base token of the chain.
+    ///
+    /// Nodes that predate custom base tokens don't support `zks_getBaseTokenL1Address`, in which
+    /// case fees are paid in ETH. Any other error of the node is returned.
+    pub async fn fetch(provider: &Provider<Http>) -> Result<Self> {
+        let l1_address = match provider.request("zks_getBaseTokenL1Address", ()).await {
+            Ok(l1_address) => l1_address,
+            Err(err) if is_method_not_found(&err) => return Ok(Self::eth()),
+            Err(err) => return Err(err).wrap_err("Failed to fetch the base token"),
+        };
+        let base_token = Self { l1_address, ..Self::eth() };
+        if base_token.is_eth() {
+            return Ok(Self::eth())
+        }
+
+        // the base token may be listed after the first page
+        for (offset, page_size) in pages(0, MAX_BASE_TOKEN_SEARCH) {
+            let page: Vec<ZkToken> = provider
+                .request("zks_getConfirmedTokens", (offset, page_size))
+                .await
+                .wrap_err("Failed to fetch confirmed tokens")?;
+            if page.iter().any(|token| token.l1_address == l1_address) {
+                return Ok(Self::from_confirmed_tokens(l1_address, &page))
+            }
+            if page.len() < page_size as usize {
+                break
+            }
+        }
+        Ok(Self::from_confirmed_tokens(l1_address, &[]))
+    }
+
+    /// Resolves the symbol and decimals of the base token with the given L1 address from the
+    /// confirmed tokens, falling back to the address if the token isn't listed
+    pub fn from_confirmed_tokens(l1_address: Address, tokens: &[ZkToken]) -> Self {
+        match tokens.iter().find(|token| token.l1_address == l1_address) {
+            Some(token) => {
+                Self { l1_address, symbol: token.symbol.clone(), decimals: token.decimals }
+            }
+            None => Self {
+                l1_address,
+                symbol: SimpleCast::to_checksum_address(&l1_address),
+                decimals: 18,
+            },
+        }
+    }
+
+    /// Formats the given fee in units of the base token, e.g. `0.0001 ETH`
+    pub fn format_fee(&self, fee: U256) -> String {
+        let amount = format_units(fee, self.decimals as u32).unwrap_or_else(|_| fee.to_string());
+        format!("{amount} {}", self.symbol)
+    }
+}
+
+/// Whether the node failed the call because it doesn't support the method
+fn is_method_not_found(err: &ProviderError) -> bool {
+    matches!(err.as_error_response(), Some(response) if response.code == METHOD_NOT_FOUND)
+}
+
+/// Splits `limit` tokens starting at `from` into `(offset, page_size)` requests.
+fn pages(from: u32, limit: u32) -> impl Iterator<Item = (u32, u8)> {
+    (0..limit).step_by(MAX_PAGE_SIZE as usize).map(move |start| {
+        let page_size = (limit - start).min(MAX_PAGE_SIZE);
+        (from + start, page_size as u8)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{spawn_json_server, spawn_rpc_node};
+
+    #[test]
+    fn can_deserialize_confirmed_tokens() {
+        let response = r#"[
+            {
+                "l1Address": "0x0000000000000000000000000000000000000000",
+                "l2Address": "0x000000000000000000000000000000000000800a",
+                "name": "Ether",
+                "symbol": "ETH",
+                "decimals": 18
+            },
+            {
+                "l1Address": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "l2Address": "0x3355df6d4c9c3035724fd0e3914de96a5a83aaf4",
+                "name": "USD Coin",
+                "symbol": "USDC",
+                "decimals": 6
+            }
+        ]"#;
+
+        let tokens: Vec<ZkToken> = serde_json::from_str(response).unwrap();
+        assert_eq!(tokens.len(), 2);
+        assert_eq!(tokens[0].symbol, "ETH");
+        assert_eq!(tokens[1].decimals, 6);
+        assert_eq!(
+            tokens[1].l2_address,
+            "0x3355df6d4c9c3035724fd0e3914de96a5a83aaf4".parse::<Address>().unwrap()
+        );
+    }
+
+    #[test]
+    fn can_resolve_custom_base_token() {
+        let tokens: Vec<ZkToken> = serde_json::from_str(
+            r#"[{
+                "l1Address": "0x5a520e593f89c908cd2bc27d928bc75913c55c42",
+                "l2Address": "0x000000000000000000000000000000000000800a",
+                "name": "Dai Stablecoin",
+                "symbol": "DAI",
+                "decimals": 18
+            }]"#,
+        )
+        .unwrap();
+        let l1_address: Address = "0x5a520e593f89c908cd2bc27d928bc75913c55c42".parse().unwrap();
+
+        let base_token = BaseToken::from_confirmed_tokens(l1_address, &tokens);
+        assert!(!base_token.is_eth());
+        assert_eq!(
+            base_token.format_fee(U256::from(1_500_000_000_000_000u64)),
+            "0.001500000000000000 DAI"
+        );
+
+        assert!(BaseToken::eth().is_eth());
+        assert!(BaseToken::eth().format_fee(U256::exp10(18)).ends_with(" ETH"));
+    }
+
+    #[test]
+    fn can_paginate_requests() {
+        assert_eq!(pages(0, 10).collect::<Vec<_>>(), vec![(0, 10)]);
+        assert_eq!(pages(5, 300).collect::<Vec<_>>(), vec![(5, 255), (260, 45)]);
+    }
+
+    #[tokio::test]
+    async fn finds_base_token_past_the_first_page() {
+        let base_token: Address = "0x5a520e593f89c908cd2bc27d928bc75913c55c42".parse().unwrap();
+        // a node listing 300 tokens, the base token being the last one
+        let url = spawn_rpc_node(move |method, params| match method {
+            "zks_getBaseTokenL1Address" => serde_json::json!(base_token),
+            "zks_getConfirmedTokens" => {
+                let offset = params[0].as_u64().unwrap();
+                let limit = params[1].as_u64().unwrap();
+                let tokens: Vec<_> = (offset..(offset + limit).min(300))
+                    .map(|id| ZkToken {
+                        l1_address: if id == 299 {
+                            base_token
+                        } else {
+                            Address::from_low_u64_be(id + 2)
+                        },
+                        l2_address: Address::from_low_u64_be(id + 2),
+                        name: format!("Token {id}"),
+                        symbol: if id == 299 { "DAI".to_string() } else { format!("T{id}") },
+                        decimals: 6,
+                    })
+                    .collect();
+                serde_json::json!(tokens)
+            }
+            _ => serde_json::Value::Null,
+        });
+
+        let provider = Provider::<Http>::try_from(url.as_str()).unwrap();
+        let fetched = BaseToken::fetch(&provider).await.unwrap();
+        assert_eq!(
+            fetched,
+            BaseToken { l1_address: base_token, symbol: "DAI".to_string(), decimals: 6 }
+        );
+    }
+
+    #[tokio::test]
+    async fn only_unsupported_base_token_calls_fall_back_to_eth() {
+        let failing_node = |code: i64| {
+            let url = spawn_json_server(move |request| {
+                let request = request.unwrap();
+                serde_json::json!({
+                    "jsonrpc": "2.0",
+                    "id": request["id"],
+                    "error": { "code": code, "message": "failed" }
+                })
+            });
+            Provider::<Http>::try_from(url.as_str()).unwrap()
+        };
+
+        // nodes predating custom base tokens
+        let base_token = BaseToken::fetch(&failing_node(METHOD_NOT_FOUND)).await.unwrap();
+        assert!(base_token.is_eth());
+
+        let err = BaseToken::fetch(&failing_node(-32000)).await.unwrap_err();
+        assert!(err.to_string().contains("Failed to fetch the base token"));
+    }
+
+    #[test]
+    fn rejects_zero_limit() {
+        assert!(ZkConfirmedTokensArgs::try_parse_from(["foundry-cli", "--limit", "0"]).is_err());
+    }
+}
@@ -0,0 +1,193 @@
+//! cast zk-withdraw subcommand
+//!
+//! Initiates a withdrawal from zkSync to L1. The base token is withdrawn through the L2 base
+//! token system contract, ERC20 tokens through the default L2 ERC20 bridge. The printed L2
+//! transaction hash is needed to finalize the withdrawal on L1 once its batch is executed.
+
+use super::zk_utils::{get_chain, get_private_key, get_rpc_url};
+use crate::opts::EthereumOpts;
+use clap::Parser;
+use eyre::WrapErr;
+use foundry_config::Config;
+use serde::Deserialize;
+use std::str::FromStr;
+use zksync_web3_rs::{
+    providers::{Http, Provider},
+    signers::{LocalWallet, Signer},
+    types::{Address, TransactionReceipt, U256},
+    zks_provider::ZKSProvider,
+    ZKSWallet,
+};
+
+/// The function of the L2 ERC20 bridge that initiates a withdrawal
+pub const L2_BRIDGE_WITHDRAW_SIG: &str = "withdraw(address,address,uint256)";
+
+/// CLI arguments for `cast zk-withdraw`.
+#[derive(Debug, Parser)]
+pub struct ZkWithdrawArgs {
+    /// The L1 address receiving the withdrawn funds.
+    #[clap(long, value_name = "L1_ADDRESS")]
+    to: Address,
+
+    /// The amount to withdraw, in the token's smallest unit.
+    #[clap(long, value_name = "AMOUNT", value_parser = parse_decimal_u256)]
+    amount: U256,
+
+    /// The L2 address of the ERC20 token to withdraw. Leave blank for the base token.
+    #[clap(long, value_name = "L2_TOKEN")]
+    token: Option<Address>,
+
+    #[clap(flatten)]
+    eth: EthereumOpts,
+}
+
+/// The bridges returned by `zks_getBridgeContracts`
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct BridgeContracts {
+    pub l1_erc20_default_bridge: Address,
+    pub l2_erc20_default_bridge: Address,
+}
+
+impl ZkWithdrawArgs {
+    pub async fn run(self) -> eyre::Result<()> {
+        let rcpt = self.withdraw().await?;
+        print_withdrawal(&rcpt);
+        Ok(())
+    }
+
+    /// Sends the withdrawal transaction and returns its receipt
+    async fn withdraw(&self) -> eyre::Result<TransactionReceipt> {
+        let private_key = get_private_key(&self.eth.wallet.private_key)?;
+        let rpc_url = get_rpc_url(&self.eth.rpc.url)?;
+        let config = Config::from(&self.eth);
+        let chain = get_chain(config.chain_id)?;
+        let provider: Provider<Http> = Provider::try_from(rpc_url)?;
+        let wallet = LocalWallet::from_str(&format!("{private_key:?}"))?.with_chain_id(chain);
+        let zk_wallet = ZKSWallet::new(wallet, None, Some(provider.clone()), None)
+            .map_err(|e| eyre::eyre!("error wallet: {e:?}"))?;
+
+        println!("Withdrawing assets....");
+        let rcpt = match self.token {
+            None => zk_wallet
+                .withdraw(self.amount, self.to)
+                .await?
+                .await?
+                .ok_or(eyre::eyre!("Error getting the receipt for withdraw"))?,
+            Some(token) => {
+                let bridges: BridgeContracts = provider
+                    .request("zks_getBridgeContracts", ())
+                    .await
+                    .wrap_err("Failed to fetch the bridge contracts")?;
+                zk_wallet
+                    .get_era_provider()?
+                    .send_eip712(
+                        &zk_wallet.l2_wallet,
+                        bridges.l2_erc20_default_bridge,
+                        L2_BRIDGE_WITHDRAW_SIG,
+                        Some(erc20_withdraw_args(self.to, token, self.amount)),
+                        None,
+                    )
+                    .await?
+                    .await?
+                    .ok_or(eyre::eyre!("Error getting the receipt for withdraw"))?
+            }
+        };
+        Ok(rcpt)
+    }
+}
+
+/// The arguments of [`L2_BRIDGE_WITHDRAW_SIG`] for withdrawing `amount` of the L2 `token` to the
+/// L1 receiver `to`
+fn erc20_withdraw_args(to: Address, token: Address, amount: U256) -> Vec<String> {
+    vec![format!("{to:?}"), format!("{token:?}"), amount.to_string()]
+}
+
+fn print_withdrawal(rcpt: &TransactionReceipt) {
+    println!("+-------------------------------------------------+");
+    println!("L2 Transaction Hash: {:#?}", rcpt.transaction_hash);
+    if let Some(block_number) = rcpt.block_number {
+        println!("Block Number: {:#?}", block_number);
+    }
+    println!("+-------------------------------------------------+");
+    println!("Finalize the withdrawal on L1 once the batch including it is executed.");
+}
+
+/// Parses a decimal string into a U256 number.
+fn parse_decimal_u256(s: &str) -> Result<U256, String> {
+    match U256::from_dec_str(s) {
+        Ok(value) => Ok(value),
+        Err(e) => Err(format!("Failed to parse decimal number: {}", e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env;
+
+    const RECEIVER: &str = "0x36615Cf349d7F6344891B1e7CA7C72883F5dc049";
+
+    #[test]
+    fn can_parse_zk_withdraw_args() {
+        let args = ZkWithdrawArgs::parse_from([
+            "foundry-cli",
+            "--to",
+            RECEIVER,
+            "--amount",
+            "1000000000",
+            "--token",
+            "0x3355df6d4c9c3035724fd0e3914de96a5a83aaf4",
+        ]);
+        assert_eq!(args.to, Address::from_str(RECEIVER).unwrap());
+        assert_eq!(args.amount, U256::from(1_000_000_000u64));
+        assert!(args.token.is_some());
+
+        assert!(ZkWithdrawArgs::try_parse_from(["foundry-cli", "--amount", "1"]).is_err());
+    }
+
+    #[test]
+    fn can_build_erc20_withdraw_args() {
+        let to = Address::from_str(RECEIVER).unwrap();
+        let token = Address::from_str("0x3355df6d4c9c3035724fd0e3914de96a5a83aaf4").unwrap();
+        assert_eq!(
+            erc20_withdraw_args(to, token, U256::from(42)),
+            vec![
+                "0x36615cf349d7f6344891b1e7ca7c72883f5dc049".to_string(),
+                "0x3355df6d4c9c3035724fd0e3914de96a5a83aaf4".to_string(),
+                "42".to_string()
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_withdraw_emits_l2_to_l1_log() {
+        let Ok(l2_url) = env::var("L2_RPC_URL") else {
+            // TODO: this test requires a running L2 to pass.
+            return
+        };
+        let private_key = "0x7726827caac94a7f9e1b160f7ea819f172f7b6f9d2a97f992c38edeab82d4110";
+        let args = ZkWithdrawArgs::parse_from([
+            "foundry-cli",
+            "--to",
+            RECEIVER,
+            "--amount",
+            "1",
+            "--rpc-url",
+            &l2_url,
+            "--private-key",
+            private_key,
+            "--chain",
+            &env::var("CHAIN").unwrap_or_else(|_| "270".to_string()),
+        ]);
+
+        let tx_hash = args.withdraw().await.unwrap().transaction_hash;
+
+        // the zkSync specific receipt fields aren't part of `TransactionReceipt`
+        let provider: Provider<Http> = Provider::try_from(l2_url).unwrap();
+        let rcpt: serde_json::Value =
+            provider.request("eth_getTransactionReceipt", [tx_hash]).await.unwrap();
+        assert_eq!(rcpt["status"], "0x1");
+        assert!(!rcpt["l2ToL1Logs"].as_array().unwrap().is_empty());
+    }
+}
@@ -1,10 +1,15 @@
 use crate::cmd::{
-    forge::build::{CoreBuildArgs, ProjectPathsArgs},
+    forge::{
+        build::{CoreBuildArgs, ProjectPathsArgs},
+        zk_build::ZkBuildArgs,
+        zksolc::zk_artifacts_dir,
+        zksolc_manager::DEFAULT_ZKSOLC_VERSION,
+    },
     Cmd, LoadConfig,
 };
 use clap::{Parser, ValueHint};
 use foundry_common::fs;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 /// CLI arguments for `forge flatten`.
 #[derive(Debug, Clone, Parser)]
@@ -24,6 +29,27 @@ pub struct FlattenArgs {
     )]
     pub output: Option<PathBuf>,
 
+    /// Target zkSync.
+    ///
+    /// The flattened source is the same on zkSync, this only selects the zksolc input for
+    /// `--standard-json`.
+    #[clap(long)]
+    pub zksync: bool,
+
+    /// Output the standard JSON input zksolc compiled the contract with, instead of the flattened
+    /// source.
+    ///
+    /// Includes the remappings and the zksolc settings of the contract, compiling it reproduces
+    /// the `zk-build` artifacts. Requires `--zksync`.
+    #[clap(long, requires = "zksync")]
+    pub standard_json: bool,
+
+    /// The zksolc version to compile the contract with for `--standard-json`.
+    ///
+    /// Defaults to the version `zk-build` uses.
+    #[clap(long, value_name = "ZK_SOLC_VERSION", requires = "standard_json")]
+    pub use_zksolc: Option<String>,
+
     #[clap(flatten)]
     project_paths: ProjectPathsArgs,
 }
@@ -31,7 +57,8 @@ pub struct FlattenArgs {
 impl Cmd for FlattenArgs {
     type Output = ();
     fn run(self) -> eyre::Result<Self::Output> {
-        let FlattenArgs { target_path, output, project_paths } = self;
+        let FlattenArgs { target_path, output, zksync, standard_json, use_zksolc, project_paths } =
+            self;
 
         // flatten is a subset of `BuildArgs` so we can reuse that to get the config
         let build_args = CoreBuildArgs {
@@ -52,13 +79,24 @@ impl Cmd for FlattenArgs {
             build_info_path: None,
         };
 
-        let config = build_args.try_load_config_emit_warnings()?;
-
-        let paths = config.project_paths();
         let target_path = dunce::canonicalize(target_path)?;
-        let flattened = paths
-            .flatten(&target_path)
-            .map_err(|err| eyre::Error::msg(format!("Failed to flatten the file: {err}")))?;
+        let flattened = if zksync && standard_json {
+            // compile the contract to get the exact input zksolc was run with
+            let zk_build = ZkBuildArgs {
+                use_zksolc: use_zksolc.unwrap_or_else(|| DEFAULT_ZKSOLC_VERSION.to_string()),
+                args: build_args,
+                ..Default::default()
+            };
+            let config = zk_build.try_load_config_emit_warnings()?;
+            let zk_out_path = zk_build.compile_with_config(&config)?;
+            zk_standard_json(&zk_out_path, &target_path)?
+        } else {
+            let config = build_args.try_load_config_emit_warnings()?;
+            let paths = config.project_paths();
+            paths
+                .flatten(&target_path)
+                .map_err(|err| eyre::Error::msg(format!("Failed to flatten the file: {err}")))?
+        };
 
         match output {
             Some(output) => {
@@ -72,3 +110,142 @@ impl Cmd for FlattenArgs {
         Ok(())
     }
 }
+
+/// Returns the standard JSON input `zk-build` compiled the given source with, as stored in `zkout/`
+fn zk_standard_json(zk_out_path: &Path, target_path: &Path) -> eyre::Result<String> {
+    let json_input = zk_artifacts_dir(zk_out_path, target_path)
+        .ok_or_else(|| eyre::eyre!("Invalid contract path: {}", target_path.display()))?
+        .join("json_input.json");
+    fs::read_to_string(&json_input).map_err(|err| {
+        eyre::eyre!("No zksolc standard JSON input found for {}: {err}", target_path.display())
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::forge::zksolc::{ZkSolc, ZkSolcOpts};
+    use foundry_config::{Config, SolcReq};
+    use serde_json::{json, Value};
+    use std::{
+        io::Write,
+        process::{Command, Stdio},
+    };
+
+    #[test]
+    fn requires_zksync_for_standard_json() {
+        assert!(FlattenArgs::try_parse_from(["foundry-cli", "src/Counter.sol", "--standard-json"])
+            .is_err());
+        let args = FlattenArgs::parse_from([
+            "foundry-cli",
+            "src/Counter.sol",
+            "--zksync",
+            "--standard-json",
+        ]);
+        assert!(args.zksync && args.standard_json);
+        assert_eq!(args.use_zksolc, None);
+
+        let args = FlattenArgs::parse_from([
+            "foundry-cli",
+            "src/Counter.sol",
+            "--zksync",
+            "--standard-json",
+            "--use-zksolc",
+            "v1.3.9",
+        ]);
+        assert_eq!(args.use_zksolc.as_deref(), Some("v1.3.9"));
+        assert!(FlattenArgs::try_parse_from([
+            "foundry-cli",
+            "src/Counter.sol",
+            "--use-zksolc",
+            "v1.3.9"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn emits_the_stored_zk_standard_json() {
+        let zk_out = tempfile::tempdir().unwrap();
+        let input = json!({
+            "language": "Solidity",
+            "sources": { "src/Counter.sol": { "content": "contract Counter {}" } },
+            "settings": {
+                "remappings": ["ds-test/=lib/ds-test/src/"],
+                "outputSelection": { "*": { "*": ["abi"], "": ["metadata"] } },
+                "forceEVMLA": true
+            }
+        });
+        let stored = serde_json::to_string_pretty(&input).unwrap();
+        fs::create_dir_all(zk_out.path().join("Counter.sol")).unwrap();
+        fs::write(zk_out.path().join("Counter.sol").join("json_input.json"), &stored).unwrap();
+
+        let emitted =
+            zk_standard_json(zk_out.path(), Path::new("/project/src/Counter.sol")).unwrap();
+        // the emitted input is byte-identical to the one zksolc compiled the contract from
+        assert_eq!(emitted, stored);
+        assert_eq!(serde_json::from_str::<Value>(&emitted).unwrap(), input);
+
+        assert!(zk_standard_json(zk_out.path(), Path::new("/project/src/Other.sol")).is_err());
+    }
+
+    #[test]
+    fn emitted_zk_standard_json_reproduces_the_artifacts() {
+        let (Ok(zksolc), Ok(solc)) = (std::env::var("ZKSOLC_PATH"), std::env::var("SOLC_PATH"))
+        else {
+            // TODO: this test requires zksolc and solc to compile with.
+            return
+        };
+        let root = tempfile::tempdir().unwrap();
+        let contract = root.path().join("src").join("Counter.sol");
+        fs::create_dir_all(contract.parent().unwrap()).unwrap();
+        fs::write(
+            &contract,
+            "pragma solidity ^0.8.0;\ncontract Counter { uint256 public number; function increment() public { number++; } }",
+        )
+        .unwrap();
+
+        let mut config = Config::load_with_root(root.path());
+        config.solc = Some(SolcReq::Local(PathBuf::from(&solc)));
+        let mut project = config.project().unwrap();
+        let zk_out = root.path().join("zkout");
+        project.paths.artifacts = zk_out.clone();
+        let opts = ZkSolcOpts {
+            compiler_path: PathBuf::from(&zksolc),
+            is_system: false,
+            settings: config.zksolc.clone(),
+            format_json: false,
+            skip_solc_version_check: false,
+            jobs: 1,
+            force: false,
+            cache: false,
+        };
+        ZkSolc::new(opts, project).compile().unwrap();
+
+        // compile the emitted input like zk-build did
+        let emitted = zk_standard_json(&zk_out, &contract).unwrap();
+        let mut child = Command::new(&zksolc)
+            .args(["--standard-json", "--solc", &solc])
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .unwrap();
+        child.stdin.take().unwrap().write_all(emitted.as_bytes()).unwrap();
+        let output = child.wait_with_output().unwrap();
+        let recompiled: Value = serde_json::from_slice(&output.stdout).unwrap();
+
+        let artifacts: Value = serde_json::from_str(
+            &fs::read_to_string(zk_out.join("Counter.sol").join("artifacts.json")).unwrap(),
+        )
+        .unwrap();
+        let bytecode = |output: &Value| {
+            output["contracts"]
+                .as_object()
+                .unwrap()
+                .values()
+                .map(|contracts| contracts["Counter"]["evm"]["bytecode"]["object"].clone())
+                .find(|bytecode| !bytecode.is_null())
+                .unwrap()
+        };
+        assert_eq!(bytecode(&recompiled), bytecode(&artifacts));
+    }
+}
@@ -1,5 +1,13 @@
 use crate::{
-    cmd::{forge::build::CoreBuildArgs, Cmd},
+    cmd::{
+        forge::{
+            build::CoreBuildArgs,
+            zk_build::ZkBuildArgs,
+            zksolc::{zk_artifacts_dir, zk_out_dir, ZKSOLC_COMPILER_INFO_KEY},
+            zksolc_manager::DEFAULT_ZKSOLC_VERSION,
+        },
+        Cmd, LoadConfig,
+    },
     opts::forge::CompilerArgs,
 };
 use clap::Parser;
@@ -18,9 +26,10 @@ use ethers::{
         utils::canonicalize,
     },
 };
-use foundry_common::compile;
+use foundry_common::{compile, fs};
+use once_cell::sync::Lazy;
 use serde_json::{to_value, Value};
-use std::fmt;
+use std::{fmt, path::Path};
 use tracing::trace;
 
 /// CLI arguments for `forge inspect`.
@@ -31,7 +40,7 @@ pub struct InspectArgs {
 
     /// The contract artifact field to inspect.
     #[clap(value_enum)]
-    pub field: ContractArtifactField,
+    pub field: InspectField,
 
     /// Pretty print the selected field, if supported.
     #[clap(long)]
@@ -50,6 +59,15 @@ impl Cmd for InspectArgs {
 
         trace!(target: "forge", ?field, ?contract, "running forge inspect");
 
+        // zksolc outputs are read from the `zk-build` artifacts instead
+        let field = match field {
+            InspectField::Zk(ZkArtifactField::CompilerInfo) => {
+                return inspect_zk_compiler_info(&contract, build)
+            }
+            InspectField::Zk(field) => return inspect_zk(&contract, field, build),
+            InspectField::Solc(field) => field,
+        };
+
         // Map field to ContractOutputSelection
         let mut cos = build.compiler.extra_output;
         if !field.is_default() && !cos.iter().any(|selected| field.eq(selected)) {
@@ -207,6 +225,100 @@ impl Cmd for InspectArgs {
     }
 }
 
+/// Compiles the project with zksolc, requesting the output of the given zk field, and prints it
+/// for the contract
+fn inspect_zk(
+    contract: &ContractInfo,
+    field: ZkArtifactField,
+    build: CoreBuildArgs,
+) -> eyre::Result<()> {
+    let output = field.zksolc_output().expect("zk field");
+    let path = contract.path.as_ref().ok_or_else(|| {
+        eyre::eyre!(
+            "Inspecting `{field}` requires the contract's path. Use `<path>:{}`",
+            contract.name
+        )
+    })?;
+
+    let zk_build = ZkBuildArgs {
+        use_zksolc: DEFAULT_ZKSOLC_VERSION.to_string(),
+        args: build,
+        ..Default::default()
+    };
+    let mut config = zk_build.try_load_config_emit_warnings()?;
+    if !config.zksolc.output_selection.iter().any(|selected| selected == output) {
+        config.zksolc.output_selection.push(output.to_string());
+    }
+    let zk_out_path = zk_build.compile_with_config(&config)?;
+
+    let artifacts_dir = zk_artifacts_dir(&zk_out_path, Path::new(path))
+        .ok_or_else(|| eyre::eyre!("Invalid contract path: {path}"))?;
+    let artifacts: Value = fs::read_json_file(&artifacts_dir.join("artifacts.json"))?;
+    println!("{}", zk_artifact_field(&artifacts, path, &contract.name, field)?);
+    Ok(())
+}
+
+/// Prints the compilers and settings recorded in the existing `zk-build` artifacts of the contract
+fn inspect_zk_compiler_info(contract: &ContractInfo, build: CoreBuildArgs) -> eyre::Result<()> {
+    let path = contract.path.as_ref().ok_or_else(|| {
+        eyre::eyre!(
+            "Inspecting `{}` requires the contract's path. Use `<path>:{}`",
+            ZkArtifactField::CompilerInfo,
+            contract.name
+        )
+    })?;
+    let config = build.try_load_config_emit_warnings()?;
+
+    let artifacts_file = zk_artifacts_dir(&zk_out_dir(&config.__root.0), Path::new(path))
+        .ok_or_else(|| eyre::eyre!("Invalid contract path: {path}"))?
+        .join("artifacts.json");
+    if !artifacts_file.exists() {
+        eyre::bail!("No zk artifact found for `{contract}`, run `forge zk-build` first")
+    }
+    let artifacts: Value = fs::read_json_file(&artifacts_file)?;
+    let info = zk_compiler_info(&artifacts, path, &contract.name)?;
+    println!("{}", serde_json::to_string_pretty(info)?);
+    Ok(())
+}
+
+/// Returns the compiler info recorded in the `zk-build` artifacts of the contract
+fn zk_compiler_info<'a>(artifacts: &'a Value, path: &str, name: &str) -> eyre::Result<&'a Value> {
+    if artifacts["contracts"][path][name].is_null() {
+        eyre::bail!("Could not find `{path}:{name}` in the zksolc output")
+    }
+    match &artifacts[ZKSOLC_COMPILER_INFO_KEY] {
+        Value::Null => eyre::bail!(
+            "The zk artifact of `{path}:{name}` does not record its compiler info, rebuild it with `forge zk-build --force`"
+        ),
+        info => Ok(info),
+    }
+}
+
+/// Returns the zksolc output of the given zk field for the contract from its `zk-build` artifacts
+fn zk_artifact_field(
+    artifacts: &Value,
+    path: &str,
+    name: &str,
+    field: ZkArtifactField,
+) -> eyre::Result<String> {
+    let contract = &artifacts["contracts"][path][name];
+    if contract.is_null() {
+        eyre::bail!("Could not find `{path}:{name}` in the zksolc output")
+    }
+    let value = match field {
+        ZkArtifactField::Assembly => &contract["evm"]["assembly"],
+        ZkArtifactField::Yul => &contract["irOptimized"],
+        ZkArtifactField::CompilerInfo => eyre::bail!("`{field}` is not a zksolc output"),
+    };
+    match value.as_str() {
+        Some(output) if !output.is_empty() => Ok(output.to_string()),
+        _ => eyre::bail!(
+            "zksolc did not generate `{}` for `{path}:{name}`. Note that no Yul is generated if the EVM legacy assembly pipeline is forced",
+            field.zksolc_output().unwrap_or_default()
+        ),
+    }
+}
+
 pub fn print_abi(abi: &LosslessAbi, pretty: bool) -> eyre::Result<()> {
     let abi_json = to_value(abi)?;
     if !pretty {
@@ -427,6 +539,79 @@ impl ContractArtifactField {
     }
 }
 
+/// Contract level output of zksolc, read from the `zk-build` artifacts
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum ZkArtifactField {
+    Assembly,
+    Yul,
+    CompilerInfo,
+}
+
+impl_value_enum! {
+    enum ZkArtifactField {
+        Assembly     => "zk-assembly" | "zkAssembly" | "zk_assembly" | "zkasm" | "zk-asm",
+        Yul          => "zk-yul" | "zkYul" | "zk_yul" | "zkyul",
+        CompilerInfo => "zk-compiler-info" | "zkCompilerInfo" | "zk_compiler_info" | "zkinfo",
+    }
+}
+
+impl fmt::Display for ZkArtifactField {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl ZkArtifactField {
+    /// Returns the zksolc output selection of this field, if it is generated when compiling.
+    pub const fn zksolc_output(&self) -> Option<&'static str> {
+        match self {
+            Self::Assembly => Some("evm.assembly"),
+            Self::Yul => Some("irOptimized"),
+            Self::CompilerInfo => None,
+        }
+    }
+}
+
+/// The field `forge inspect` prints, either a solc or a zksolc output
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum InspectField {
+    Solc(ContractArtifactField),
+    Zk(ZkArtifactField),
+}
+
+impl ::clap::ValueEnum for InspectField {
+    fn value_variants<'a>() -> &'a [Self] {
+        static ALL: Lazy<Vec<InspectField>> = Lazy::new(|| {
+            let solc = ContractArtifactField::ALL.iter().copied().map(InspectField::Solc);
+            solc.chain(ZkArtifactField::ALL.iter().copied().map(InspectField::Zk)).collect()
+        });
+        &ALL
+    }
+
+    fn to_possible_value(&self) -> Option<::clap::builder::PossibleValue> {
+        match self {
+            Self::Solc(field) => field.to_possible_value(),
+            Self::Zk(field) => field.to_possible_value(),
+        }
+    }
+
+    fn from_str(input: &str, ignore_case: bool) -> Result<Self, String> {
+        let _ = ignore_case;
+        <Self as ::std::str::FromStr>::from_str(input)
+    }
+}
+
+impl ::std::str::FromStr for InspectField {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.parse() {
+            Ok(field) => Ok(Self::Zk(field)),
+            Err(_) => s.parse().map(Self::Solc),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -445,4 +630,80 @@ mod tests {
             }
         }
     }
+
+    #[test]
+    fn can_parse_inspect_fields() {
+        for &field in ContractArtifactField::ALL {
+            assert_eq!(field.as_str().parse::<InspectField>().unwrap(), InspectField::Solc(field));
+        }
+        for &field in ZkArtifactField::ALL {
+            assert_eq!(field.to_string(), field.as_str());
+            for alias in std::iter::once(field.as_str()).chain(field.aliases().iter().copied()) {
+                assert_eq!(alias.parse::<InspectField>().unwrap(), InspectField::Zk(field));
+            }
+        }
+        assert!("zk-bytecode".parse::<InspectField>().is_err());
+
+        let args = InspectArgs::parse_from(["foundry-cli", "src/Counter.sol:Counter", "zk-yul"]);
+        assert_eq!(args.field, InspectField::Zk(ZkArtifactField::Yul));
+    }
+
+    #[test]
+    fn can_read_zk_outputs() {
+        let artifacts = serde_json::json!({
+            "contracts": {
+                "src/Counter.sol": {
+                    "Counter": {
+                        "evm": { "assembly": "\t.text\n\t.file\t\"Counter.sol:Counter\"\n" },
+                        "irOptimized": ""
+                    }
+                }
+            }
+        });
+        let field = |field| zk_artifact_field(&artifacts, "src/Counter.sol", "Counter", field);
+
+        assert!(field(ZkArtifactField::Assembly).unwrap().contains("Counter.sol:Counter"));
+        assert!(field(ZkArtifactField::Yul)
+            .unwrap_err()
+            .to_string()
+            .contains("zksolc did not generate `irOptimized`"));
+        assert!(zk_artifact_field(
+            &artifacts,
+            "src/Counter.sol",
+            "Other",
+            ZkArtifactField::Assembly
+        )
+        .unwrap_err()
+        .to_string()
+        .contains("Could not find"));
+    }
+
+    #[test]
+    fn can_read_zk_compiler_info() {
+        // as recorded by `zk-build` with `optimizer_mode = "z"` and `append_cbor = false`
+        let info = serde_json::json!({
+            "zksolcVersion": "1.3.11",
+            "solcVersion": "0.8.20",
+            "optimizer": { "enabled": true, "runs": 200, "mode": "z" },
+            "systemMode": false,
+            "forceEVMLA": false,
+            "metadataHash": "none"
+        });
+        let mut artifacts = serde_json::json!({
+            "contracts": { "src/Counter.sol": { "Counter": { "abi": [] } } },
+            "compilerInfo": info
+        });
+
+        let recorded = zk_compiler_info(&artifacts, "src/Counter.sol", "Counter").unwrap();
+        assert_eq!(recorded, &info);
+        assert_eq!(recorded["optimizer"]["mode"], "z");
+        assert_eq!(recorded["metadataHash"], "none");
+
+        assert!(zk_compiler_info(&artifacts, "src/Counter.sol", "Other").is_err());
+        artifacts.as_object_mut().unwrap().remove("compilerInfo");
+        assert!(zk_compiler_info(&artifacts, "src/Counter.sol", "Counter")
+            .unwrap_err()
+            .to_string()
+            .contains("does not record its compiler info"));
+    }
 }
@@ -18,6 +18,7 @@ use std::path::PathBuf;
 mod etherscan;
 pub mod provider;
 mod sourcify;
+mod zksync;
 
 /// Verification provider arguments
 #[derive(Debug, Clone, Parser)]
@@ -179,6 +180,8 @@ pub struct VerifyCheckArgs {
     /// For Etherscan - Submission GUID.
     ///
     /// For Sourcify - Contract Address.
+    ///
+    /// For zkSync - Verification ID.
     id: String,
 
     #[clap(flatten)]
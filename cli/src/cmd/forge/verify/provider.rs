@@ -1,6 +1,6 @@
 use crate::cmd::forge::verify::{
-    etherscan::EtherscanVerificationProvider, sourcify::SourcifyVerificationProvider, VerifyArgs,
-    VerifyCheckArgs,
+    etherscan::EtherscanVerificationProvider, sourcify::SourcifyVerificationProvider,
+    zksync::ZkSyncVerificationProvider, VerifyArgs, VerifyCheckArgs,
 };
 use async_trait::async_trait;
 use std::{fmt, str::FromStr};
@@ -32,6 +32,7 @@ impl FromStr for VerificationProviderType {
             "e" | "etherscan" => Ok(VerificationProviderType::Etherscan),
             "s" | "sourcify" => Ok(VerificationProviderType::Sourcify),
             "b" | "blockscout" => Ok(VerificationProviderType::Blockscout),
+            "z" | "zksync" => Ok(VerificationProviderType::ZkSync),
             _ => Err(format!("Unknown provider: {s}")),
         }
     }
@@ -49,6 +50,9 @@ impl fmt::Display for VerificationProviderType {
             VerificationProviderType::Blockscout => {
                 write!(f, "blockscout")?;
             }
+            VerificationProviderType::ZkSync => {
+                write!(f, "zksync")?;
+            }
         };
         Ok(())
     }
@@ -60,6 +64,8 @@ pub enum VerificationProviderType {
     Etherscan,
     Sourcify,
     Blockscout,
+    #[value(name = "zksync")]
+    ZkSync,
 }
 
 impl VerificationProviderType {
@@ -78,6 +84,7 @@ impl VerificationProviderType {
             VerificationProviderType::Blockscout => {
                 Ok(Box::<EtherscanVerificationProvider>::default())
             }
+            VerificationProviderType::ZkSync => Ok(Box::<ZkSyncVerificationProvider>::default()),
         }
     }
 }
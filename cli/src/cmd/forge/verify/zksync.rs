@@ -0,0 +1,263 @@
+use super::{VerifyArgs, VerifyCheckArgs};
+use crate::cmd::{
+    forge::{
+        verify::provider::VerificationProvider,
+        zksolc::{zk_artifacts_dir, zk_out_dir},
+    },
+    retry::RETRY_CHECK_ON_VERIFY,
+    LoadConfig,
+};
+use async_trait::async_trait;
+use cast::SimpleCast;
+use ethers::types::Address;
+use eyre::{eyre, Context};
+use foundry_common::fs;
+use foundry_utils::Retry;
+use futures::FutureExt;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::path::{Path, PathBuf};
+use tracing::{trace, warn};
+
+/// The code format the zkSync explorer expects for standard json submissions
+pub static ZKSYNC_CODE_FORMAT: &str = "solidity-standard-json-input";
+
+/// The type that can verify a contract on the zkSync block explorer
+///
+/// Verification is submitted with the exact standard json input `forge zk-build` stored next to
+/// the contract's artifacts in `zkout/`, so the explorer recompiles the very same input that
+/// produced the deployed bytecode.
+#[derive(Debug, Clone, Default)]
+#[non_exhaustive]
+pub struct ZkSyncVerificationProvider;
+
+#[async_trait]
+impl VerificationProvider for ZkSyncVerificationProvider {
+    async fn preflight_check(&mut self, args: VerifyArgs) -> eyre::Result<()> {
+        let _ = self.verifier_url(&args.verifier.verifier_url)?;
+        let _ = self.prepare_request(&args)?;
+        Ok(())
+    }
+
+    async fn verify(&mut self, args: VerifyArgs) -> eyre::Result<()> {
+        let verifier_url = self.verifier_url(&args.verifier.verifier_url)?;
+        let body = self.prepare_request(&args)?;
+
+        trace!(target : "forge::verify", ?body, "submitting zksync verification request");
+
+        let client = reqwest::Client::new();
+        let retry: Retry = args.retry.into();
+        let verification_id = retry
+            .run_async(|| {
+                async {
+                    println!(
+                        "\nSubmitting verification for [{}] {:?}.",
+                        body.contract_name,
+                        SimpleCast::to_checksum_address(&args.address)
+                    );
+                    let response = client
+                        .post(verifier_url.clone())
+                        .header("Content-Type", "application/json")
+                        .body(serde_json::to_string(&body)?)
+                        .send()
+                        .await?;
+
+                    let status = response.status();
+                    let text = response.text().await?;
+                    if !status.is_success() {
+                        warn!("Failed verify submission: {:?}", text);
+                        eyre::bail!(
+                            "zkSync verification request for address ({:?}) failed with status code {status}\nDetails: {text}",
+                            args.address,
+                        );
+                    }
+
+                    Ok(text.trim().trim_matches('"').to_string())
+                }
+                .boxed()
+            })
+            .await?;
+
+        println!("Submitted contract for verification:\n\tVerification ID: `{verification_id}`");
+
+        if args.watch {
+            let check_args = VerifyCheckArgs {
+                id: verification_id,
+                etherscan: args.etherscan.clone(),
+                retry: RETRY_CHECK_ON_VERIFY,
+                verifier: args.verifier.clone(),
+            };
+            self.check(check_args).await?;
+        }
+
+        if let Some(url) = explorer_address_url(args.etherscan.chain.map(|c| c.id()), args.address)
+        {
+            println!("Explorer: {url}");
+        }
+
+        Ok(())
+    }
+
+    async fn check(&self, args: VerifyCheckArgs) -> eyre::Result<()> {
+        let verifier_url = self.verifier_url(&args.verifier.verifier_url)?;
+        let url = format!("{}/{}", verifier_url.trim_end_matches('/'), args.id);
+
+        let retry: Retry = args.retry.into();
+        retry
+            .run_async(|| {
+                async {
+                    let response = reqwest::get(&url).await?;
+                    if !response.status().is_success() {
+                        eyre::bail!(
+                            "Failed to request verification status with status code {}",
+                            response.status()
+                        );
+                    }
+
+                    let resp = response.json::<ZkSyncVerificationStatus>().await?;
+                    trace!(target : "forge::verify", ?resp, "Received verification status");
+
+                    match resp.status.as_str() {
+                        "successful" => {
+                            println!("Contract successfully verified");
+                            Ok(())
+                        }
+                        "failed" => {
+                            eprintln!(
+                                "Contract failed to verify.\nDetails: `{}`",
+                                resp.error.unwrap_or_default()
+                            );
+                            if let Some(errors) = resp.compilation_errors {
+                                errors.iter().for_each(|err| eprintln!("{err}"));
+                            }
+                            std::process::exit(1);
+                        }
+                        status => Err(eyre!("Verification is still pending ({status})...")),
+                    }
+                }
+                .boxed()
+            })
+            .await
+            .wrap_err("Checking verification result failed:")
+    }
+}
+
+impl ZkSyncVerificationProvider {
+    /// The zkSync explorer has no canonical default endpoint, so the URL must always be provided.
+    fn verifier_url(&self, verifier_url: &Option<String>) -> eyre::Result<String> {
+        verifier_url.clone().ok_or_else(|| {
+            eyre!("zkSync verification requires the explorer's endpoint. Try using --verifier-url")
+        })
+    }
+
+    /// Configures the API request to the zkSync explorer using the given [`VerifyArgs`].
+    ///
+    /// Reads the standard json input and the compiler output `forge zk-build` wrote for the
+    /// contract's source file.
+    fn prepare_request(&self, args: &VerifyArgs) -> eyre::Result<ZkSyncVerifyRequest> {
+        let config = args.try_load_config_emit_warnings()?;
+        let contract_path = args.contract.path.clone().ok_or_else(|| {
+            eyre!(
+                "zkSync verification requires the contract's path. Use `<path>:{}`",
+                args.contract.name
+            )
+        })?;
+
+        let artifacts_dir = contract_artifacts_dir(&config.__root.0, &contract_path)?;
+        let source_code: Value = fs::read_json_file(&artifacts_dir.join("json_input.json"))
+            .wrap_err("Could not read the zksolc standard json input - did you run zk-build?")?;
+        let output: Value = fs::read_json_file(&artifacts_dir.join("artifacts.json"))
+            .wrap_err("Could not read the zksolc compiler output - did you run zk-build?")?;
+
+        let compiler_zksolc_version = output["zk_version"]
+            .as_str()
+            .map(|version| format!("v{}", version.trim_start_matches('v')))
+            .ok_or_else(|| eyre!("No zksolc version found in the compiler output"))?;
+        let compiler_solc_version = match args.compiler_version.as_ref() {
+            Some(version) => version.trim_start_matches('v').to_string(),
+            None => output["version"]
+                .as_str()
+                .map(|version| version.split('+').next().unwrap_or(version).to_string())
+                .ok_or_else(|| eyre!("No solc version found in the compiler output"))?,
+        };
+        let optimization_used =
+            source_code["settings"]["optimizer"]["enabled"].as_bool().unwrap_or_default();
+
+        let constructor_arguments = match args.constructor_args.as_ref() {
+            Some(args) => format!("0x{}", args.trim_start_matches("0x")),
+            None => "0x".to_string(),
+        };
+
+        Ok(ZkSyncVerifyRequest {
+            contract_address: format!("{:?}", args.address),
+            source_code,
+            code_format: ZKSYNC_CODE_FORMAT.to_string(),
+            contract_name: format!("{contract_path}:{}", args.contract.name),
+            compiler_zksolc_version,
+            compiler_solc_version,
+            optimization_used,
+            constructor_arguments,
+        })
+    }
+}
+
+/// Returns the directory in `zkout/` where `forge zk-build` stored the output for the given source
+fn contract_artifacts_dir(root: &Path, contract_path: &str) -> eyre::Result<PathBuf> {
+    zk_artifacts_dir(&zk_out_dir(root), Path::new(contract_path))
+        .ok_or_else(|| eyre!("Invalid contract path: {contract_path}"))
+}
+
+/// Returns the explorer URL of the given address, for known zkSync networks
+fn explorer_address_url(chain_id: Option<u64>, address: Address) -> Option<String> {
+    let explorer = match chain_id? {
+        324 => "https://explorer.zksync.io",
+        280 => "https://goerli.explorer.zksync.io",
+        _ => return None,
+    };
+    Some(format!("{explorer}/address/{}#contract", SimpleCast::to_checksum_address(&address)))
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkSyncVerifyRequest {
+    contract_address: String,
+    source_code: Value,
+    code_format: String,
+    contract_name: String,
+    compiler_zksolc_version: String,
+    compiler_solc_version: String,
+    optimization_used: bool,
+    constructor_arguments: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkSyncVerificationStatus {
+    status: String,
+    #[serde(default)]
+    error: Option<String>,
+    #[serde(default)]
+    compilation_errors: Option<Vec<String>>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_build_explorer_url() {
+        let address: Address = "0x36615Cf349d7F6344891B1e7CA7C72883F5dc049".parse().unwrap();
+        assert_eq!(
+            explorer_address_url(Some(324), address).unwrap(),
+            "https://explorer.zksync.io/address/0x36615Cf349d7F6344891B1e7CA7C72883F5dc049#contract"
+        );
+        assert!(explorer_address_url(Some(1), address).is_none());
+        assert!(explorer_address_url(None, address).is_none());
+    }
+
+    #[test]
+    fn can_resolve_zk_artifacts_dir() {
+        let dir = contract_artifacts_dir(Path::new("/project"), "src/nested/Counter.sol").unwrap();
+        assert_eq!(dir, PathBuf::from("/project/zkout/Counter.sol"));
+    }
+}
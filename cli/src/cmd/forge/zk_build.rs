@@ -27,14 +27,16 @@
 /// complexities.
 use super::build::CoreBuildArgs;
 use super::{
-    zksolc::{ZkSolc, ZkSolcOpts},
+    zksolc::{zk_out_dir, ZkSolc, ZkSolcOpts},
     zksolc_manager::{
         ZkSolcManager, ZkSolcManagerBuilder, ZkSolcManagerOpts, DEFAULT_ZKSOLC_VERSION,
     },
 };
 use crate::cmd::{Cmd, LoadConfig};
-use clap::Parser;
-use ethers::prelude::Project;
+use clap::{Parser, ValueHint};
+use comfy_table::Table;
+use ethers::prelude::{info::ContractInfo, Project};
+use foundry_common::fs;
 use foundry_config::{
     figment::{
         self,
@@ -44,8 +46,13 @@ use foundry_config::{
     },
     Config,
 };
-use serde::Serialize;
-use std::fmt::Debug;
+use serde::{Deserialize, Serialize};
+use std::{
+    collections::BTreeMap,
+    fmt::{self, Debug},
+    path::{Path, PathBuf},
+};
+use yansi::Paint;
 
 foundry_config::merge_impl_figment_convert!(ZkBuildArgs, args);
 
@@ -68,6 +75,27 @@ foundry_config::merge_impl_figment_convert!(ZkBuildArgs, args);
 ///   experimental and contained more bugs than today. This flag allows us to use the EVM legacy
 ///   assembly pipeline, which can be beneficial in certain situations.
 ///
+/// * `fail_on_missing_libraries`: A boolean flag indicating whether the build fails if zksolc reports
+///   libraries that are not linked, instead of producing incomplete artifacts.
+///
+/// * `skip_solc_version_check`: A boolean flag to compile even if the zksolc version doesn't
+///   support the required solc version.
+///
+/// * `format_json`: A boolean flag indicating whether to print the compiler errors and warnings as
+///   a JSON report instead of human readable messages.
+///
+/// * `artifact_format`: The format of the artifacts to write, `hardhat` additionally writes
+///   artifacts that hardhat-zksync deploy scripts can consume.
+///
+/// * `size_breakdown`: An optional contract to report the bytecode size contribution of each of
+///   its functions for.
+///
+/// * `emit_deploy_manifest`: An optional path to write a manifest of the deployable contracts to
+///   after a successful build.
+///
+/// * `diff_against`: An optional previously emitted manifest to check the new build against for
+///   changes that break upgrades.
+///
 /// * `args`: Core build arguments encapsulated in the `CoreBuildArgs` struct. These include
 ///   additional parameters required for building the contract, such as optimization level, output
 ///   directory etc.
@@ -103,15 +131,99 @@ pub struct ZkBuildArgs {
     pub is_system: bool,
 
     /// A flag indicating whether to forcibly switch to the EVM legacy assembly pipeline.
+    ///
+    /// Overrides `zksolc.force_evmla`, per contract settings in `zksolc.contracts` still apply.
     #[clap(
         help_heading = "ZkSync Compiler options",
         help = "Forcibly switch to the EVM legacy assembly pipeline. It is useful for older revisions of `solc` 0.8, where
         Yul was considered highly experimental and contained more bugs than today",
         long = "force-evmla",
+        visible_alias = "zk-force-evmla",
         value_name = "FORCE_EVMLA"
     )]
+    #[serde(skip)]
     pub force_evmla: bool,
 
+    /// Fail the build if zksolc reports libraries that are not linked.
+    ///
+    /// Overrides `zksolc.fail_on_missing_libraries`.
+    #[clap(
+        help_heading = "ZkSync Compiler options",
+        long = "zk-fail-on-missing-libraries",
+        alias = "fail-on-missing-libraries"
+    )]
+    #[serde(skip)]
+    pub fail_on_missing_libraries: bool,
+
+    /// Compile even if zksolc doesn't support the required solc version, for experimentation.
+    #[clap(help_heading = "ZkSync Compiler options", long = "zk-skip-solc-version-check")]
+    #[serde(skip)]
+    pub skip_solc_version_check: bool,
+
+    /// The maximum number of zksolc processes to run concurrently.
+    ///
+    /// Defaults to the number of available CPUs.
+    #[clap(
+        help_heading = "ZkSync Compiler options",
+        long,
+        short = 'j',
+        value_name = "JOBS",
+        value_parser = clap::value_parser!(usize).range(1..)
+    )]
+    #[serde(skip)]
+    pub jobs: Option<usize>,
+
+    /// Print the compiler errors and warnings as JSON.
+    #[clap(help_heading = "ZkSync Compiler options", long = "format-json")]
+    #[serde(skip)]
+    pub format_json: bool,
+
+    /// The format of the artifacts to write.
+    ///
+    /// The native artifacts are always written, `hardhat` additionally writes an artifact per
+    /// contract that hardhat-zksync deploy scripts can consume.
+    #[clap(
+        help_heading = "ZkSync Compiler options",
+        long = "artifact-format",
+        value_enum,
+        default_value = "native",
+        value_name = "FORMAT"
+    )]
+    #[serde(skip)]
+    pub artifact_format: ZkArtifactFormat,
+
+    /// Report the approximate contribution of each function of the given contract to its
+    /// bytecode size, in the form `(<path>:)?<contractname>`.
+    #[clap(
+        help_heading = "ZkSync Compiler options",
+        long = "size-breakdown",
+        value_name = "CONTRACT"
+    )]
+    #[serde(skip)]
+    pub size_breakdown: Option<ContractInfo>,
+
+    /// Write a manifest of every deployable contract's bytecode hash, factory dependencies and
+    /// constructor ABI to the given path after a successful build.
+    #[clap(
+        help_heading = "ZkSync Compiler options",
+        long = "emit-deploy-manifest",
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath
+    )]
+    #[serde(skip)]
+    pub emit_deploy_manifest: Option<PathBuf>,
+
+    /// Compare the build to a manifest written with `--emit-deploy-manifest`, failing if it
+    /// changes the storage layout of a contract or removes one of its functions.
+    #[clap(
+        help_heading = "ZkSync Compiler options",
+        long = "diff-against",
+        value_name = "MANIFEST",
+        value_hint = ValueHint::FilePath
+    )]
+    #[serde(skip)]
+    pub diff_against: Option<PathBuf>,
+
     /// Core build arguments encapsulated in the `CoreBuildArgs` struct.
     #[clap(flatten)]
     #[serde(flatten)]
@@ -136,30 +248,105 @@ impl Cmd for ZkBuildArgs {
     /// 5. If the zkSync Solidity compiler does not exist in the compilers directory, it triggers
     ///    its download.
     /// 6. Initiates the contract compilation process using the `ZkSolc` compiler. This process is
-    ///    configured with the `is_system` and `force_evmla` parameters from the `ZkBuildArgs`
-    ///    instance, and the path to the zkSync Solidity compiler.
+    ///    configured with the `is_system` parameter from the `ZkBuildArgs` instance, the `zksolc`
+    ///    section of the config, and the path to the zkSync Solidity compiler.
     /// 7. If the compilation process fails, it raises an error and halts execution.
+    /// 8. If `size_breakdown` is set, reports the size contribution of the contract's functions.
+    /// 9. With the `hardhat` artifact format, writes the hardhat-zksync artifacts.
+    /// 10. If `emit_deploy_manifest` is set, writes the manifest of the deployable contracts.
+    /// 11. If `diff_against` is set, reports the breaking changes against the given manifest and
+    ///     fails if there are any.
     ///
     /// The method returns `Ok(())` if the entire process completes successfully, or an error if any
     /// step in the process fails. The purpose of this function is to consolidate all steps
     /// involved in the zkSync contract compilation process in a single method, allowing for
     /// easy invocation of the process with a single function call.
     fn run(self) -> eyre::Result<()> {
-        let config = self.try_load_config_emit_warnings()?;
+        let mut config = self.try_load_config_emit_warnings()?;
+        // the breakdown is derived from the zkEVM assembly
+        if self.size_breakdown.is_some() &&
+            !config.zksolc.output_selection.iter().any(|output| output == "evm.assembly")
+        {
+            config.zksolc.output_selection.push("evm.assembly".to_string());
+        }
+        // the manifest records the storage layouts to diff against
+        if (self.emit_deploy_manifest.is_some() || self.diff_against.is_some()) &&
+            !config.zksolc.output_selection.iter().any(|output| output == "storageLayout")
+        {
+            config.zksolc.output_selection.push("storageLayout".to_string());
+        }
+        let zk_out_path = self.compile_with_config(&config)?;
+
+        if let Some(contract) = &self.size_breakdown {
+            print_size_breakdown(&zk_out_path, contract)?;
+        }
+
+        if self.artifact_format == ZkArtifactFormat::Hardhat {
+            let hardhat_dir = zk_out_path.join(HARDHAT_ARTIFACTS_DIR);
+            for (path, artifact) in hardhat_artifacts(&zk_out_path)? {
+                let path = hardhat_dir.join(path);
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(&path, serde_json::to_string_pretty(&artifact)?)?;
+            }
+        }
+
+        if let Some(manifest_path) = &self.emit_deploy_manifest {
+            let manifest = deploy_manifest(&zk_out_path)?;
+            if let Some(parent) = manifest_path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(manifest_path, serde_json::to_string_pretty(&manifest)?)?;
+            if !self.format_json {
+                println!("Deploy manifest written at {}", manifest_path.display());
+            }
+        }
+
+        if let Some(previous_path) = &self.diff_against {
+            let previous: BTreeMap<String, DeployManifestEntry> =
+                fs::read_json_file(previous_path)?;
+            let changes = breaking_changes(&previous, &deploy_manifest(&zk_out_path)?);
+            if !changes.is_empty() {
+                for change in &changes {
+                    println!("{}", Paint::red(format!("Breaking change: {change}")));
+                }
+                eyre::bail!(
+                    "Found {} breaking change(s) against {}",
+                    changes.len(),
+                    previous_path.display()
+                )
+            }
+            if !self.format_json {
+                println!("No breaking changes against {}", previous_path.display());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl ZkBuildArgs {
+    /// Compiles the project of the given config with zksolc, returning the `zkout` directory the
+    /// artifacts were written to.
+    ///
+    /// This allows callers to adjust the config, e.g. the `zksolc` output selection, before
+    /// compiling.
+    pub fn compile_with_config(&self, config: &Config) -> eyre::Result<PathBuf> {
         let mut project = config.project()?;
 
         //set zk out path
-        let zk_out_path = project.paths.root.join("zkout");
-        project.paths.artifacts = zk_out_path;
+        let zk_out_path = zk_out_dir(&project.paths.root);
+        project.paths.artifacts = zk_out_path.clone();
 
         let zksolc_manager = self.setup_zksolc_manager()?;
 
-        println!("Compiling smart contracts...");
-        self.compile_smart_contracts(zksolc_manager, project)
+        if !self.format_json {
+            println!("Compiling smart contracts...");
+        }
+        self.compile_smart_contracts(zksolc_manager, project, config)?;
+        Ok(zk_out_path)
     }
-}
 
-impl ZkBuildArgs {
     /// The `setup_zksolc_manager` function creates and prepares an instance of `ZkSolcManager`.
     ///
     /// It follows these steps:
@@ -199,7 +386,8 @@ impl ZkBuildArgs {
     /// The `compile_smart_contracts` function initiates the contract compilation process.
     ///
     /// It follows these steps:
-    /// 1. Create an instance of `ZkSolcOpts` with the appropriate options.
+    /// 1. Create an instance of `ZkSolcOpts` with the appropriate options, including the
+    ///    `zksolc` section of the config and whether the build is forced or cached.
     /// 2. Instantiate `ZkSolc` with the created options and the project.
     /// 3. Initiate the contract compilation process.
     ///
@@ -209,18 +397,28 @@ impl ZkBuildArgs {
         &self,
         zksolc_manager: ZkSolcManager,
         project: Project,
+        config: &Config,
     ) -> eyre::Result<()> {
         let zksolc_opts = ZkSolcOpts {
             compiler_path: zksolc_manager.get_full_compiler_path(),
             is_system: self.is_system,
-            force_evmla: self.force_evmla,
+            settings: config.zksolc.clone(),
+            format_json: self.format_json,
+            skip_solc_version_check: self.skip_solc_version_check,
+            jobs: self.jobs.unwrap_or_else(|| {
+                std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1)
+            }),
+            force: config.force,
+            cache: config.cache,
         };
 
         let zksolc = ZkSolc::new(zksolc_opts, project);
 
         match zksolc.compile() {
             Ok(_) => {
-                println!("Compiled Successfully");
+                if !self.format_json {
+                    println!("Compiled Successfully");
+                }
                 Ok(())
             }
             Err(err) => {
@@ -230,6 +428,301 @@ impl ZkBuildArgs {
     }
 }
 
+/// A deployable contract in the manifest written with `--emit-deploy-manifest`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DeployManifestEntry {
+    pub bytecode_hash: String,
+    /// The contracts that must be known to the chain before deploying this one, by bytecode hash
+    pub factory_deps: BTreeMap<String, String>,
+    /// The constructor of the contract's ABI, if it declares one
+    pub constructor: Option<serde_json::Value>,
+    /// The selectors of the contract's functions, by signature
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub method_identifiers: BTreeMap<String, String>,
+    /// The contract's state variables, in declaration order
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub storage_layout: Vec<DeployManifestSlot>,
+}
+
+/// A state variable in the storage layout of a [`DeployManifestEntry`]
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DeployManifestSlot {
+    pub label: String,
+    pub slot: String,
+    pub offset: u64,
+    /// The type of the variable, e.g. `uint128` or `mapping(address => uint256)`
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+impl fmt::Display for DeployManifestSlot {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{} {}` at slot {} offset {}", self.ty, self.label, self.slot, self.offset)
+    }
+}
+
+/// Returns every contract in the `zk-build` artifacts in `zk_out_path` as `(path, name, output)`,
+/// sorted by path and name.
+fn zk_contracts(zk_out_path: &Path) -> eyre::Result<Vec<(String, String, serde_json::Value)>> {
+    let mut contracts = Vec::new();
+    for entry in std::fs::read_dir(zk_out_path)? {
+        let artifacts_file = entry?.path().join("artifacts.json");
+        if !artifacts_file.is_file() {
+            continue
+        }
+        let mut artifacts: serde_json::Value = fs::read_json_file(&artifacts_file)?;
+        let Some(files) = artifacts["contracts"].as_object_mut() else { continue };
+        for (path, file_contracts) in std::mem::take(files) {
+            let serde_json::Value::Object(file_contracts) = file_contracts else { continue };
+            for (name, contract) in file_contracts {
+                contracts.push((path.clone(), name, contract));
+            }
+        }
+    }
+    contracts.sort_by(|(a_path, a_name, _), (b_path, b_name, _)| {
+        (a_path, a_name).cmp(&(b_path, b_name))
+    });
+    Ok(contracts)
+}
+
+/// Returns the factory dependencies of a contract's zksolc output, by bytecode hash
+fn factory_deps(contract: &serde_json::Value) -> BTreeMap<String, String> {
+    contract["factoryDependencies"]
+        .as_object()
+        .map(|deps| {
+            deps.iter()
+                .map(|(hash, dep)| (hash.clone(), dep.as_str().unwrap_or_default().to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Returns the state variables of a contract's zksolc `storageLayout` output, with their types
+/// resolved to the names of the types
+fn storage_layout(contract: &serde_json::Value) -> Vec<DeployManifestSlot> {
+    let layout = &contract["storageLayout"];
+    let Some(storage) = layout["storage"].as_array() else { return Vec::new() };
+    storage
+        .iter()
+        .map(|slot| {
+            let ty = slot["type"].as_str().unwrap_or_default();
+            DeployManifestSlot {
+                label: slot["label"].as_str().unwrap_or_default().to_string(),
+                slot: slot["slot"].as_str().unwrap_or_default().to_string(),
+                offset: slot["offset"].as_u64().unwrap_or_default(),
+                ty: layout["types"][ty]["label"].as_str().unwrap_or(ty).to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the changes of the `current` manifest that break upgrading the contracts of the
+/// `previous` one: removed contracts and functions, and state variables that were removed or
+/// changed their type or position. Appending state variables is not a breaking change.
+pub fn breaking_changes(
+    previous: &BTreeMap<String, DeployManifestEntry>,
+    current: &BTreeMap<String, DeployManifestEntry>,
+) -> Vec<String> {
+    let mut changes = Vec::new();
+    for (contract, previous) in previous {
+        let Some(current) = current.get(contract) else {
+            changes.push(format!("{contract} was removed"));
+            continue
+        };
+        for signature in previous.method_identifiers.keys() {
+            if !current.method_identifiers.contains_key(signature) {
+                changes.push(format!("{contract} removed the function `{signature}`"));
+            }
+        }
+        for (idx, slot) in previous.storage_layout.iter().enumerate() {
+            match current.storage_layout.get(idx) {
+                Some(new_slot) if new_slot == slot => {}
+                Some(new_slot) => changes
+                    .push(format!("{contract} replaced the state variable {slot} with {new_slot}")),
+                None => changes.push(format!("{contract} removed the state variable {slot}")),
+            }
+        }
+    }
+    changes
+}
+
+/// Collects the deployable contracts from the `zk-build` artifacts in `zk_out_path`, keyed by
+/// `<path>:<name>`.
+///
+/// Contracts without a bytecode hash, e.g. interfaces or builds that did not select
+/// `evm.bytecode`, are not deployable and left out.
+pub fn deploy_manifest(zk_out_path: &Path) -> eyre::Result<BTreeMap<String, DeployManifestEntry>> {
+    let mut manifest = BTreeMap::new();
+    for (path, name, contract) in zk_contracts(zk_out_path)? {
+        let Some(bytecode_hash) = contract["hash"].as_str() else { continue };
+        let constructor = contract["abi"]
+            .as_array()
+            .and_then(|abi| abi.iter().find(|item| item["type"] == "constructor").cloned());
+        manifest.insert(
+            format!("{path}:{name}"),
+            DeployManifestEntry {
+                bytecode_hash: bytecode_hash.to_string(),
+                factory_deps: factory_deps(&contract),
+                constructor,
+                method_identifiers: contract["evm"]["methodIdentifiers"]
+                    .as_object()
+                    .map(|methods| {
+                        methods
+                            .iter()
+                            .map(|(sig, id)| (sig.clone(), id.as_str().unwrap_or_default().into()))
+                            .collect()
+                    })
+                    .unwrap_or_default(),
+                storage_layout: storage_layout(&contract),
+            },
+        );
+    }
+    Ok(manifest)
+}
+
+/// The size of a zkEVM instruction in bytes
+const ZKEVM_INSTRUCTION_SIZE: usize = 8;
+
+/// The size of a constant in the zkEVM bytecode in bytes
+const ZKEVM_CELL_SIZE: usize = 32;
+
+/// The entry constants are attributed to in the size breakdown
+pub const SIZE_BREAKDOWN_CONSTANTS: &str = "<constants>";
+
+/// The approximate number of bytes each function contributes to the bytecode of a contract,
+/// derived from its zkEVM assembly.
+///
+/// Every instruction is counted as 8 bytes and every constant as 32 bytes, ignoring padding.
+/// Returns the entries sorted by size, largest first.
+pub fn size_breakdown(assembly: &str) -> Vec<(String, usize)> {
+    let mut sizes: BTreeMap<String, usize> = BTreeMap::new();
+    let mut current: Option<String> = None;
+    let mut in_constants = false;
+    for line in assembly.lines() {
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with(';') {
+            continue
+        }
+        if !line.starts_with(char::is_whitespace) {
+            // a non local label starts a function, unless it names a constant
+            if let Some(label) = trimmed.strip_suffix(':') {
+                if !label.starts_with('.') && !in_constants {
+                    current = Some(label.to_string());
+                }
+            }
+            continue
+        }
+        if let Some(directive) = trimmed.strip_prefix('.') {
+            let directive = directive.split_whitespace().next().unwrap_or_default();
+            match directive {
+                "text" => in_constants = false,
+                "rodata" | "data" => in_constants = true,
+                "cell" => {
+                    *sizes.entry(SIZE_BREAKDOWN_CONSTANTS.to_string()).or_default() +=
+                        ZKEVM_CELL_SIZE
+                }
+                _ => {}
+            }
+            continue
+        }
+        if !in_constants {
+            let function = current.clone().unwrap_or_else(|| "<unknown>".to_string());
+            *sizes.entry(function).or_default() += ZKEVM_INSTRUCTION_SIZE;
+        }
+    }
+    let mut sizes: Vec<_> = sizes.into_iter().collect();
+    sizes.sort_by(|(a_name, a_size), (b_name, b_size)| b_size.cmp(a_size).then(a_name.cmp(b_name)));
+    sizes
+}
+
+/// Returns the size breakdown of the given contract from the `zk-build` artifacts in
+/// `zk_out_path` as `(path:name, bytecode size, breakdown)`, the breakdown is empty if no assembly
+/// was generated for it
+fn contract_size_breakdown(
+    zk_out_path: &Path,
+    contract: &ContractInfo,
+) -> eyre::Result<(String, usize, Vec<(String, usize)>)> {
+    let (path, name, output) = zk_contracts(zk_out_path)?
+        .into_iter()
+        .find(|(path, name, _)| {
+            name == &contract.name && contract.path.as_ref().map_or(true, |p| p == path)
+        })
+        .ok_or_else(|| eyre::eyre!("Could not find contract {contract} in the zksolc output"))?;
+    let total = output["evm"]["bytecode"]["object"]
+        .as_str()
+        .map(|object| object.trim_start_matches("0x").len() / 2)
+        .unwrap_or_default();
+    let breakdown = output["evm"]["assembly"].as_str().map(size_breakdown).unwrap_or_default();
+    Ok((format!("{path}:{name}"), total, breakdown))
+}
+
+/// Prints the size breakdown of the given contract from the `zk-build` artifacts in
+/// `zk_out_path`, or only its total size if no assembly was generated for it
+fn print_size_breakdown(zk_out_path: &Path, contract: &ContractInfo) -> eyre::Result<()> {
+    let (id, total, breakdown) = contract_size_breakdown(zk_out_path, contract)?;
+    println!("Size breakdown of {id} ({total} bytes)");
+    if breakdown.is_empty() {
+        println!("No assembly was generated for {}, only its total size is known", contract.name);
+        return Ok(())
+    }
+
+    let mut table = Table::new();
+    table.set_header(vec!["Function", "Size (B)", "Share"]);
+    for (function, size) in breakdown {
+        let share = if total == 0 { 0.0 } else { size as f64 * 100.0 / total as f64 };
+        table.add_row(vec![function, size.to_string(), format!("{share:.1}%")]);
+    }
+    println!("{table}");
+    Ok(())
+}
+
+/// The format of the artifact files written by `zk-build`
+#[derive(clap::ValueEnum, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ZkArtifactFormat {
+    /// The zksolc output of every source file, in `zkout/<file>/artifacts.json`.
+    #[default]
+    Native,
+    /// Additionally write an artifact per contract in the layout of hardhat-zksync, in
+    /// `zkout/hardhat/<path>/<name>.json`.
+    Hardhat,
+}
+
+/// The `_format` of hardhat-zksync artifacts
+pub const HARDHAT_ZKSOLC_ARTIFACT_FORMAT: &str = "hh-zksolc-artifact-1";
+
+/// The directory in `zkout/` hardhat-zksync artifacts are written to
+pub const HARDHAT_ARTIFACTS_DIR: &str = "hardhat";
+
+/// Converts the `zk-build` artifacts in `zk_out_path` to hardhat-zksync artifacts, returning the
+/// path of each artifact relative to the hardhat artifacts directory along with the artifact.
+///
+/// Like hardhat-zksync, the deployed bytecode is the bytecode, the constructor is part of it on
+/// zkSync. Libraries are linked by zksolc at deploy time, so there are no link references.
+pub fn hardhat_artifacts(zk_out_path: &Path) -> eyre::Result<Vec<(PathBuf, serde_json::Value)>> {
+    let mut artifacts = Vec::new();
+    for (path, name, contract) in zk_contracts(zk_out_path)? {
+        let bytecode = match contract["evm"]["bytecode"]["object"].as_str() {
+            Some(object) if object.starts_with("0x") => object.to_string(),
+            Some(object) => format!("0x{object}"),
+            None => "0x".to_string(),
+        };
+        let artifact = serde_json::json!({
+            "_format": HARDHAT_ZKSOLC_ARTIFACT_FORMAT,
+            "contractName": name,
+            "sourceName": path,
+            "abi": contract.get("abi").cloned().unwrap_or_else(|| serde_json::json!([])),
+            "bytecode": bytecode,
+            "deployedBytecode": bytecode,
+            "linkReferences": {},
+            "deployedLinkReferences": {},
+            "factoryDeps": factory_deps(&contract),
+        });
+        artifacts.push((Path::new(&path).join(format!("{name}.json")), artifact));
+    }
+    Ok(artifacts)
+}
+
 // Make this args a `figment::Provider` so that it can be merged into the `Config`
 impl Provider for ZkBuildArgs {
     fn metadata(&self) -> Metadata {
@@ -239,7 +732,18 @@ impl Provider for ZkBuildArgs {
     fn data(&self) -> Result<Map<Profile, Dict>, figment::Error> {
         let value = Value::serialize(self)?;
         let error = InvalidType(value.to_actual(), "map".into());
-        let dict = value.into_dict().ok_or(error)?;
+        let mut dict = value.into_dict().ok_or(error)?;
+
+        let mut zksolc = Dict::new();
+        if self.force_evmla {
+            zksolc.insert("force_evmla".to_string(), true.into());
+        }
+        if self.fail_on_missing_libraries {
+            zksolc.insert("fail_on_missing_libraries".to_string(), true.into());
+        }
+        if !zksolc.is_empty() {
+            dict.insert("zksolc".to_string(), zksolc.into());
+        }
 
         // if self.names {
         //     dict.insert("names".to_string(), true.into());
@@ -252,3 +756,273 @@ impl Provider for ZkBuildArgs {
         Ok(Map::from([(Config::selected_profile(), dict)]))
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn can_parse_zk_force_evmla() {
+        let args: ZkBuildArgs = ZkBuildArgs::parse_from(["foundry-cli", "--zk-force-evmla"]);
+        assert!(args.force_evmla);
+
+        let config = Config::from(&args);
+        assert!(config.zksolc.force_evmla);
+
+        let args: ZkBuildArgs = ZkBuildArgs::parse_from(["foundry-cli"]);
+        let config = Config::from(&args);
+        assert!(!config.zksolc.force_evmla);
+        assert!(!config.zksolc.fail_on_missing_libraries);
+    }
+
+    #[test]
+    fn can_parse_zk_fail_on_missing_libraries() {
+        let args: ZkBuildArgs = ZkBuildArgs::parse_from([
+            "foundry-cli",
+            "--zk-fail-on-missing-libraries",
+            "--zk-force-evmla",
+        ]);
+        let config = Config::from(&args);
+        assert!(config.zksolc.fail_on_missing_libraries);
+        assert!(config.zksolc.force_evmla);
+    }
+
+    #[test]
+    fn can_build_deploy_manifest() {
+        let zk_out = tempfile::tempdir().unwrap();
+        let write_artifacts = |filename: &str, artifacts: serde_json::Value| {
+            let dir = zk_out.path().join(filename);
+            fs::create_dir_all(&dir).unwrap();
+            fs::write(dir.join("artifacts.json"), artifacts.to_string()).unwrap();
+        };
+        write_artifacts(
+            "Factory.sol",
+            serde_json::json!({ "contracts": { "src/Factory.sol": {
+                "Factory": {
+                    "abi": [
+                        { "type": "function", "name": "deploy", "inputs": [], "outputs": [] },
+                        { "type": "constructor", "inputs": [{ "name": "owner", "type": "address" }] }
+                    ],
+                    "hash": "0x0100001f",
+                    "factoryDependencies": { "0x01000021": "src/Child.sol:Child" }
+                },
+                "IFactory": { "abi": [] }
+            }}}),
+        );
+        write_artifacts(
+            "Child.sol",
+            serde_json::json!({ "contracts": { "src/Child.sol": {
+                "Child": { "abi": [], "hash": "0x01000021", "factoryDependencies": {} }
+            }}}),
+        );
+        fs::write(zk_out.path().join("zksolc-cache.json"), "{}").unwrap();
+
+        let manifest = deploy_manifest(zk_out.path()).unwrap();
+        assert_eq!(
+            manifest.keys().collect::<Vec<_>>(),
+            vec!["src/Child.sol:Child", "src/Factory.sol:Factory"]
+        );
+
+        let factory = &manifest["src/Factory.sol:Factory"];
+        assert_eq!(factory.bytecode_hash, "0x0100001f");
+        assert_eq!(factory.factory_deps["0x01000021"], "src/Child.sol:Child");
+        assert_eq!(factory.constructor.as_ref().unwrap()["inputs"][0]["name"], "owner");
+
+        let child = &manifest["src/Child.sol:Child"];
+        assert!(child.factory_deps.is_empty());
+        assert!(child.constructor.is_none());
+
+        // the manifest is sorted, so it diffs cleanly between builds
+        let json = serde_json::to_string_pretty(&manifest).unwrap();
+        assert!(json.find("src/Child.sol:Child").unwrap() < json.find("src/Factory.sol").unwrap());
+    }
+
+    #[test]
+    fn diff_flags_changed_storage_layout() {
+        let contract = |storage: serde_json::Value| {
+            serde_json::json!({
+                "hash": "0x0100002b",
+                "evm": { "methodIdentifiers": { "balance()": "b69ef8a8", "deposit()": "d0e30db0" } },
+                "storageLayout": {
+                    "storage": storage,
+                    "types": {
+                        "t_address": { "label": "address" },
+                        "t_uint128": { "label": "uint128" },
+                        "t_uint256": { "label": "uint256" }
+                    }
+                }
+            })
+        };
+        let slot = |label: &str, slot: &str, offset: u64, ty: &str| serde_json::json!({ "label": label, "slot": slot, "offset": offset, "type": ty });
+        let manifest = |contract: serde_json::Value| {
+            let zk_out = tempfile::tempdir().unwrap();
+            let dir = zk_out.path().join("Vault.sol");
+            fs::create_dir_all(&dir).unwrap();
+            let artifacts =
+                serde_json::json!({ "contracts": { "src/Vault.sol": { "Vault": contract } } });
+            fs::write(dir.join("artifacts.json"), artifacts.to_string()).unwrap();
+            deploy_manifest(zk_out.path()).unwrap()
+        };
+
+        let previous = manifest(contract(serde_json::json!([
+            slot("owner", "0", 0, "t_address"),
+            slot("total", "1", 0, "t_uint256"),
+        ])));
+        let vault = &previous["src/Vault.sol:Vault"];
+        assert_eq!(vault.method_identifiers["deposit()"], "d0e30db0");
+        assert_eq!(vault.storage_layout[1].ty, "uint256");
+
+        // the manifest round trips, so it can be diffed against later
+        let json = serde_json::to_string(&previous).unwrap();
+        let stored: BTreeMap<String, DeployManifestEntry> = serde_json::from_str(&json).unwrap();
+        assert_eq!(stored, previous);
+
+        // appending a state variable keeps the layout compatible
+        let appended = manifest(contract(serde_json::json!([
+            slot("owner", "0", 0, "t_address"),
+            slot("total", "1", 0, "t_uint256"),
+            slot("fee", "2", 0, "t_uint128"),
+        ])));
+        assert!(breaking_changes(&stored, &appended).is_empty());
+
+        // inserting one shifts the following variables
+        let inserted = manifest(contract(serde_json::json!([
+            slot("owner", "0", 0, "t_address"),
+            slot("fee", "1", 0, "t_uint128"),
+            slot("total", "2", 0, "t_uint256"),
+        ])));
+        let changes = breaking_changes(&stored, &inserted);
+        assert_eq!(
+            changes,
+            vec!["src/Vault.sol:Vault replaced the state variable `uint256 total` at slot 1 offset 0 with `uint128 fee` at slot 1 offset 0"]
+        );
+
+        let mut removed = inserted.clone();
+        let vault = removed.get_mut("src/Vault.sol:Vault").unwrap();
+        vault.method_identifiers.remove("deposit()");
+        vault.storage_layout.clear();
+        let changes = breaking_changes(&stored, &removed);
+        assert!(
+            changes.contains(&"src/Vault.sol:Vault removed the function `deposit()`".to_string())
+        );
+        assert!(changes
+            .iter()
+            .any(|change| change.contains("removed the state variable `address owner`")));
+        assert_eq!(
+            breaking_changes(&stored, &BTreeMap::new()),
+            vec!["src/Vault.sol:Vault was removed"]
+        );
+    }
+
+    #[test]
+    fn can_write_hardhat_artifacts() {
+        let zk_out = tempfile::tempdir().unwrap();
+        let dir = zk_out.path().join("Greeter.sol");
+        fs::create_dir_all(&dir).unwrap();
+        let artifacts = serde_json::json!({ "contracts": { "src/Greeter.sol": {
+            "Greeter": {
+                "abi": [{ "type": "function", "name": "greet", "inputs": [], "outputs": [] }],
+                "evm": { "bytecode": { "object": "0000008003000039" } },
+                "hash": "0x0100000b",
+                "factoryDependencies": { "0x01000021": "src/Child.sol:Child" }
+            }
+        }}});
+        fs::write(dir.join("artifacts.json"), artifacts.to_string()).unwrap();
+
+        let artifacts = hardhat_artifacts(zk_out.path()).unwrap();
+        assert_eq!(artifacts.len(), 1);
+        let (path, artifact) = &artifacts[0];
+        assert_eq!(path, &Path::new("src/Greeter.sol").join("Greeter.json"));
+
+        // the fields hardhat-zksync artifacts are made of
+        for field in [
+            "_format",
+            "contractName",
+            "sourceName",
+            "abi",
+            "bytecode",
+            "deployedBytecode",
+            "linkReferences",
+            "deployedLinkReferences",
+            "factoryDeps",
+        ] {
+            assert!(artifact.get(field).is_some(), "missing {field}");
+        }
+        assert_eq!(artifact["_format"], HARDHAT_ZKSOLC_ARTIFACT_FORMAT);
+        assert_eq!(artifact["contractName"], "Greeter");
+        assert_eq!(artifact["sourceName"], "src/Greeter.sol");
+        assert_eq!(artifact["bytecode"], "0x0000008003000039");
+        assert_eq!(artifact["deployedBytecode"], artifact["bytecode"]);
+        assert_eq!(artifact["factoryDeps"]["0x01000021"], "src/Child.sol:Child");
+
+        let args = ZkBuildArgs::parse_from(["foundry-cli", "--artifact-format", "hardhat"]);
+        assert_eq!(args.artifact_format, ZkArtifactFormat::Hardhat);
+        assert_eq!(
+            ZkBuildArgs::parse_from(["foundry-cli"]).artifact_format,
+            ZkArtifactFormat::Native
+        );
+    }
+
+    #[test]
+    fn can_break_down_contract_size() {
+        let assembly = "\t.text
+\t.file\t\"Counter.sol:Counter\"
+\t.globl\t__entry
+__entry:
+.func_begin0:
+\tadd\t128, r0, r3
+\tst.1\t64, r3
+\tand!\t1, r2, r2
+\tjump.ne\t@.BB0_1
+.BB0_1:
+\tnear_call\tr0, @fun_increment_23, @DEFAULT_UNWIND
+\tret
+.func_end0:
+
+fun_increment_23:
+.func_begin1:
+\tadd\tstack[@ptr_calldata], r0, r1
+\tret
+.func_end1:
+\t.note.GNU-stack
+\t.rodata
+CPI0_0:
+\t.cell 26959946667150639794667015087019630673637144422540572481103610249215
+CPI0_1:
+\t.cell 0
+";
+        let breakdown = size_breakdown(assembly);
+        assert_eq!(
+            breakdown,
+            vec![
+                (SIZE_BREAKDOWN_CONSTANTS.to_string(), 2 * ZKEVM_CELL_SIZE),
+                ("__entry".to_string(), 6 * ZKEVM_INSTRUCTION_SIZE),
+                ("fun_increment_23".to_string(), 2 * ZKEVM_INSTRUCTION_SIZE),
+            ]
+        );
+
+        // an artifact of the contract, whose bytecode is padded to an odd number of words
+        let zk_out = tempfile::tempdir().unwrap();
+        let artifacts = zk_out.path().join("Counter.sol");
+        std::fs::create_dir_all(&artifacts).unwrap();
+        let artifact = serde_json::json!({ "contracts": { "src/Counter.sol": { "Counter": {
+            "evm": { "bytecode": { "object": "00".repeat(5 * 32) }, "assembly": assembly }
+        }}}});
+        std::fs::write(artifacts.join("artifacts.json"), artifact.to_string()).unwrap();
+        let contract: ContractInfo = "src/Counter.sol:Counter".parse().unwrap();
+        let (id, total, from_artifact) = contract_size_breakdown(zk_out.path(), &contract).unwrap();
+        assert_eq!(id, "src/Counter.sol:Counter");
+        assert_eq!(total, 5 * 32);
+        assert_eq!(from_artifact, breakdown);
+
+        // the entries add up to the size of the code before padding
+        let code_size: usize = from_artifact.iter().map(|(_, size)| size).sum();
+        let mut words = (code_size + 31) / 32;
+        if words % 2 == 0 {
+            words += 1;
+        }
+        assert_eq!(words * 32, total);
+
+        assert!(size_breakdown("").is_empty());
+    }
+}
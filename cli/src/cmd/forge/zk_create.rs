@@ -36,6 +36,12 @@
 /// 7. Printing contract address, transaction hash, gas used, gas price, and block number if
 ///    the deployment is successful
 ///
+/// With `--libraries-file`, the contract is first recompiled against the already deployed
+/// libraries at the addresses of the file.
+///
+/// With `--dry-run`, nothing is sent: the address the contract would be deployed to, its bytecode
+/// hash, its factory dependencies and the fee estimated via `zks_estimateFee` are printed instead.
+///
 /// To use the `forge zk-create` command:
 /// 1. Parse the command-line arguments using the `ZkCreateArgs::parse()` method
 /// 2. Execute the deployment process by calling the `run()` method on the parsed arguments
@@ -46,28 +52,57 @@
 /// - `zksync`
 use crate::{
     cmd::{
-        cast::zk_utils::{get_chain, get_private_key, get_rpc_url},
-        forge::build::CoreBuildArgs,
+        cast::{
+            zk_address::compute_create_address,
+            zk_utils::{get_chain, get_private_key, get_rpc_url},
+        },
+        forge::{
+            build::CoreBuildArgs,
+            verify,
+            zk_build::ZkBuildArgs,
+            zksolc::{zk_bytecode_hash, ZKSOLC_COMPILER_INFO_KEY},
+            zksolc_manager::DEFAULT_ZKSOLC_VERSION,
+        },
         read_constructor_args_file,
+        retry::RetryArgs,
+        LoadConfig,
     },
-    opts::{EthereumOpts, TransactionOpts},
+    opts::{EthereumOpts, EtherscanOpts, TransactionOpts},
+    utils,
 };
 use clap::{Parser, ValueHint};
 use ethers::{
-    abi::Abi,
+    abi::{self, Abi, Token},
     solc::{info::ContractInfo, Project},
-    types::Bytes,
+    types::{Address, Bytes, H256, U256},
+    utils::id,
 };
 use eyre::Context;
+use foundry_common::abi::parse_tokens;
 use foundry_config::Config;
+use rustc_hex::ToHex;
 use serde_json::Value;
-use std::{fs, path::PathBuf, str::FromStr};
+use std::{
+    collections::{BTreeMap, BTreeSet},
+    fs,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
 use zksync_web3_rs::{
     providers::Provider,
     signers::{LocalWallet, Signer},
     ZKSWallet,
 };
 
+/// The system contract deployments are sent to
+pub const CONTRACT_DEPLOYER_ADDRESS: &str = "0x0000000000000000000000000000000000008006";
+
+/// The system contract keeping the deployment nonces of accounts
+pub const NONCE_HOLDER_ADDRESS: &str = "0x0000000000000000000000000000000000008003";
+
+/// The gas per pubdata byte deployments are estimated with
+pub const DEFAULT_GAS_PER_PUBDATA_LIMIT: u64 = 50_000;
+
 /// CLI arguments for `forge zk-create`.
 /// Struct `ZkCreateArgs` encapsulates the arguments necessary for creating a new zkSync contract.
 ///
@@ -134,6 +169,31 @@ pub struct ZkCreateArgs {
     )]
     factory_deps: Option<Vec<ContractInfo>>,
 
+    /// A JSON file mapping already deployed libraries to their addresses, to link the contract
+    /// against before deploying it.
+    ///
+    /// Libraries are named either `<path>:<libraryname>` or `<libraryname>`, e.g.
+    /// `{"src/Math.sol:Math": "0x..."}`.
+    #[clap(
+        long,
+        help_heading = "ZkSync Features",
+        value_hint = ValueHint::FilePath,
+        value_name = "FILE"
+    )]
+    libraries_file: Option<PathBuf>,
+
+    /// Verify the contract after creation.
+    ///
+    /// The verification is submitted with the standard json input that was used to compile the
+    /// deployed bytecode, e.g. `--verify --verifier zksync --verifier-url <URL>`.
+    #[clap(long, help = "Verify contract after creation.")]
+    verify: bool,
+
+    /// Print the address the contract would be deployed to, its bytecode hash, factory
+    /// dependencies and estimated fee, without sending the deployment.
+    #[clap(long, conflicts_with = "verify")]
+    dry_run: bool,
+
     /// Core build arguments.
     #[clap(flatten)]
     opts: CoreBuildArgs,
@@ -145,6 +205,14 @@ pub struct ZkCreateArgs {
     /// Ethereum-specific options, such as the network and wallet.
     #[clap(flatten)]
     eth: EthereumOpts,
+
+    /// Verification provider options, such as the verifier and its URL.
+    #[clap(flatten)]
+    verifier: verify::VerifierArgs,
+
+    /// Retry options for the contract verification.
+    #[clap(flatten)]
+    retry: RetryArgs,
 }
 
 impl ZkCreateArgs {
@@ -153,15 +221,19 @@ impl ZkCreateArgs {
     /// # Procedure
     /// 1. Retrieves private key, RPC URL, and chain information from the current instance.
     /// 2. It then sets up the project and artifact paths.
-    /// 3. Retrieves the bytecode of the contract.
-    /// 4. If factory dependencies are present, they are processed.
-    /// 5. A signer is created using the private key and chain.
-    /// 6. The ABI of the contract is obtained.
-    /// 7. The constructor arguments are encoded.
-    /// 8. A wallet is set up using the signer and the RPC URL.
-    /// 9. The contract deployment is started.
-    /// 10. If deployment is successful, the contract address, transaction hash, gas used, gas
+    /// 3. If a libraries file is given, recompiles the contract linked against its libraries.
+    /// 4. Retrieves the bytecode of the contract.
+    /// 5. If factory dependencies are present, they are processed.
+    /// 6. A signer is created using the private key and chain.
+    /// 7. The ABI of the contract is obtained.
+    /// 8. The constructor arguments are encoded.
+    /// 9. A wallet is set up using the signer and the RPC URL.
+    /// 10. The contract deployment is started.
+    /// 11. If deployment is successful, the contract address, transaction hash, gas used, gas
     ///     price, and block number are printed to the console.
+    /// 12. If `--verify` is set, the deployed contract is submitted for verification.
+    ///
+    /// With `--dry-run`, the deployment is estimated and printed after step 8 instead.
     pub async fn run(self) -> eyre::Result<()> {
         let private_key = get_private_key(&self.eth.wallet.private_key)?;
         let rpc_url = get_rpc_url(&self.eth.rpc.url)?;
@@ -170,6 +242,10 @@ impl ZkCreateArgs {
         let mut project = self.opts.project()?;
         project.paths.artifacts = project.paths.root.join("zkout");
 
+        if let Some(libraries_file) = &self.libraries_file {
+            self.link_libraries(&project, libraries_file)?;
+        }
+
         let bytecode = match Self::get_bytecode_from_contract(&project, &self.contract) {
             Ok(bytecode) => bytecode,
             Err(e) => {
@@ -200,6 +276,35 @@ impl ZkCreateArgs {
 
         let constructor_args = self.get_constructor_args(&contract);
 
+        if self.dry_run {
+            let constructor_input = Self::encode_constructor_args(&contract, &constructor_args)?
+                .map(hex::decode)
+                .transpose()?
+                .unwrap_or_default();
+            let dry_run = self
+                .dry_run(
+                    &format!("{private_key:?}"),
+                    &bytecode,
+                    factory_dependencies.as_deref().unwrap_or_default(),
+                    &constructor_input,
+                )
+                .await?;
+            print_dry_run(&dry_run);
+            return Ok(())
+        }
+
+        // Before we actually deploy the contract we check if the verify settings are valid
+        let encoded_constructor_args = if self.verify {
+            let encoded_args = Self::encode_constructor_args(&contract, &constructor_args)?;
+            // NOTE: the address is not known yet, so this is not the exact request that is sent
+            // after the deployment
+            let verify = self.verify_args(Address::zero(), encoded_args.clone(), chain.id());
+            verify.verification_provider()?.preflight_check(verify).await?;
+            encoded_args
+        } else {
+            None
+        };
+
         let provider = Provider::try_from(rpc_url)?;
         let wallet = LocalWallet::from_str(&format!("{private_key:?}"))?.with_chain_id(chain);
         let zk_wallet = ZKSWallet::new(wallet, None, Some(provider), None)?;
@@ -221,7 +326,154 @@ impl ZkCreateArgs {
         println!("Block Number: {:#?}", block_number);
         println!("+-------------------------------------------------+");
 
-        Ok(())
+        if !self.verify {
+            return Ok(())
+        }
+
+        println!("Starting contract verification...");
+        let verify = self.verify_args(deployed_address, encoded_constructor_args, chain.id());
+        println!("Waiting for {} to detect contract deployment...", verify.verifier.verifier);
+        verify.run().await
+    }
+
+    /// Estimates the deployment of the contract without sending it.
+    ///
+    /// The address is derived from the sender's deployment nonce, which zkSync keeps separately
+    /// from the transaction nonce, and the fee is estimated via `zks_estimateFee` for a call to
+    /// the `ContractDeployer` with the contract and its factory dependencies.
+    async fn dry_run(
+        &self,
+        private_key: &str,
+        bytecode: &[u8],
+        factory_deps: &[Vec<u8>],
+        constructor_input: &[u8],
+    ) -> eyre::Result<ZkDryRun> {
+        let sender =
+            ethers::signers::Signer::address(&ethers::signers::LocalWallet::from_str(private_key)?);
+        let config = Config::from(&self.eth);
+        let provider = utils::get_provider(&config)?;
+
+        let nonce_call =
+            [id("getDeploymentNonce(address)").to_vec(), abi::encode(&[Token::Address(sender)])]
+                .concat();
+        let nonce: Bytes = provider
+            .request(
+                "eth_call",
+                (
+                    serde_json::json!({
+                        "to": NONCE_HOLDER_ADDRESS,
+                        "data": format!("0x{}", hex::encode(nonce_call)),
+                    }),
+                    "latest",
+                ),
+            )
+            .await
+            .wrap_err("Failed to fetch the deployment nonce")?;
+        let nonce = U256::from_big_endian(&nonce);
+
+        let bytecode_hash = H256(zk_bytecode_hash(bytecode));
+        let calldata = [
+            id("create(bytes32,bytes32,bytes)").to_vec(),
+            abi::encode(&[
+                Token::FixedBytes(vec![0; 32]),
+                Token::FixedBytes(bytecode_hash.as_bytes().to_vec()),
+                Token::Bytes(constructor_input.to_vec()),
+            ]),
+        ]
+        .concat();
+        // the contract itself must be known to the chain to be deployed
+        let deps: Vec<&[u8]> =
+            std::iter::once(bytecode).chain(factory_deps.iter().map(Vec::as_slice)).collect();
+        let fee: Value = provider
+            .request(
+                "zks_estimateFee",
+                [serde_json::json!({
+                    "from": format!("{sender:?}"),
+                    "to": CONTRACT_DEPLOYER_ADDRESS,
+                    "data": format!("0x{}", hex::encode(calldata)),
+                    "eip712Meta": {
+                        "gasPerPubdata": format!("{:#x}", DEFAULT_GAS_PER_PUBDATA_LIMIT),
+                        "factoryDeps": deps,
+                    },
+                })],
+            )
+            .await
+            .wrap_err("Failed to estimate the deployment fee, is this a zkSync node?")?;
+        let gas_limit: U256 = serde_json::from_value(fee["gas_limit"].clone())
+            .wrap_err("Invalid gas limit in the estimated fee")?;
+        let max_fee_per_gas: U256 = serde_json::from_value(fee["max_fee_per_gas"].clone())
+            .wrap_err("Invalid max fee per gas in the estimated fee")?;
+
+        Ok(ZkDryRun {
+            address: compute_create_address(sender, nonce),
+            bytecode_hash,
+            factory_deps: factory_deps.iter().map(|dep| H256(zk_bytecode_hash(dep))).collect(),
+            gas_limit,
+            max_fee_per_gas,
+            fee: gas_limit * max_fee_per_gas,
+        })
+    }
+
+    /// This function builds the verification arguments for a deployed contract.
+    ///
+    /// # Arguments
+    ///
+    /// * `address` - The address the contract was deployed to.
+    /// * `constructor_args` - The hex encoded constructor arguments, if any.
+    /// * `chain` - The ID of the chain the contract was deployed on.
+    ///
+    /// # Returns
+    ///
+    /// A `VerifyArgs` instance targeting the deployed contract with the configured verifier.
+    fn verify_args(
+        &self,
+        address: Address,
+        constructor_args: Option<String>,
+        chain: u64,
+    ) -> verify::VerifyArgs {
+        let num_of_optimizations =
+            if self.opts.compiler.optimize { self.opts.compiler.optimizer_runs } else { None };
+        verify::VerifyArgs {
+            address,
+            contract: self.contract.clone(),
+            compiler_version: None,
+            constructor_args,
+            constructor_args_path: None,
+            num_of_optimizations,
+            etherscan: EtherscanOpts {
+                key: self.eth.etherscan.key.clone(),
+                chain: Some(chain.into()),
+            },
+            flatten: false,
+            force: false,
+            watch: true,
+            retry: self.retry,
+            libraries: vec![],
+            root: self.opts.project_paths.root.clone(),
+            verifier: self.verifier.clone(),
+            show_standard_json_input: false,
+        }
+    }
+
+    /// This function ABI-encodes the constructor arguments of the contract.
+    ///
+    /// # Returns
+    ///
+    /// The hex encoded constructor arguments, or `None` if the contract has no constructor or no
+    /// arguments were provided.
+    fn encode_constructor_args(abi: &Abi, args: &[String]) -> eyre::Result<Option<String>> {
+        let constructor = match abi.constructor() {
+            Some(constructor) if !args.is_empty() => constructor,
+            _ => return Ok(None),
+        };
+        let params = constructor
+            .inputs
+            .iter()
+            .zip(args)
+            .map(|(input, arg)| (&input.kind, arg.as_str()))
+            .collect::<Vec<_>>();
+        let tokens = parse_tokens(params, true)?;
+        Ok(Some(constructor.encode_input(Vec::new(), &tokens)?.to_hex::<String>()))
     }
 
     /// This function retrieves the constructor arguments for the contract.
@@ -296,6 +548,103 @@ impl ZkCreateArgs {
         ))
     }
 
+    /// Recompiles the project with the libraries the contract is missing linked to the addresses
+    /// of the libraries file.
+    ///
+    /// # Errors
+    /// If the file has no address for a library of the contract, or an address for a library the
+    /// contract doesn't use, the error names the library.
+    fn link_libraries(&self, project: &Project, libraries_file: &Path) -> eyre::Result<()> {
+        let (zk_build, config) = self.relink_build(project, libraries_file)?;
+        zk_build.compile_with_config(&config)?;
+
+        if let Some(library) = Self::get_missing_libraries(project, &self.contract)?.first() {
+            eyre::bail!("Library {library} is still not linked after recompiling")
+        }
+        Ok(())
+    }
+
+    /// Returns the build linking the libraries the contract is missing, and its config, with the
+    /// compiler settings recorded in the artifacts of the contract.
+    ///
+    /// Artifacts that don't record them are rebuilt with the defaults of `zk-build`.
+    ///
+    /// # Errors
+    /// If the libraries can't be linked, see [`Self::link_libraries`], or the config can't
+    /// reproduce the recorded `forceEVMLA` of the contract.
+    fn relink_build(
+        &self,
+        project: &Project,
+        libraries_file: &Path,
+    ) -> eyre::Result<(ZkBuildArgs, Config)> {
+        let addresses: BTreeMap<String, Address> =
+            foundry_common::fs::read_json_file(libraries_file)?;
+        let missing = Self::get_missing_libraries(project, &self.contract)?;
+        let libraries = library_links(&missing, &addresses)?;
+
+        let compiler_info = Self::get_compiler_info(project, &self.contract)?;
+        let source = Path::new(self.contract.path.as_deref().unwrap_or_default());
+        let source = source.strip_prefix(&project.paths.root).unwrap_or(source);
+        let force_evmla = compiler_info["forceEVMLA"].as_bool().unwrap_or_default();
+        let zk_build = ZkBuildArgs {
+            use_zksolc: match compiler_info["zksolcVersion"].as_str() {
+                Some(version) => format!("v{version}"),
+                None => DEFAULT_ZKSOLC_VERSION.to_string(),
+            },
+            // zk-build turns on the system mode of `is-system` sources by itself
+            is_system: compiler_info["systemMode"].as_bool().unwrap_or_default() &&
+                !source.to_string_lossy().contains("is-system"),
+            force_evmla,
+            // the recorded compilers already built the contract
+            skip_solc_version_check: true,
+            args: self.opts.clone(),
+            ..Default::default()
+        };
+        let mut config = zk_build.try_load_config_emit_warnings()?;
+        if config.zksolc.force_evmla_for(source) != force_evmla {
+            eyre::bail!(
+                "{} was built with forceEVMLA = {force_evmla}, which its `[zksolc.contracts]` \
+                 settings no longer reproduce",
+                source.display()
+            )
+        }
+        config.libraries.extend(libraries);
+        Ok((zk_build, config))
+    }
+
+    /// Returns the compilers and settings recorded in the artifacts of the contract, `null` for
+    /// artifacts of older builds
+    fn get_compiler_info(project: &Project, contract_info: &ContractInfo) -> eyre::Result<Value> {
+        let output_path = Self::get_path_for_contract_output(project, contract_info);
+        let data = fs::read_to_string(&output_path).wrap_err(format!(
+            "Unable to read contract output file at {} - did you run zk-build",
+            output_path.display()
+        ))?;
+        let output: Value = serde_json::from_str(&data)
+            .wrap_err(format!("Unable to parse JSON contract from {}", output_path.display()))?;
+        Ok(output[ZKSOLC_COMPILER_INFO_KEY].clone())
+    }
+
+    /// Returns the libraries zksolc reported as missing for the contract, e.g. `src/Math.sol:Math`
+    fn get_missing_libraries(
+        project: &Project,
+        contract_info: &ContractInfo,
+    ) -> eyre::Result<Vec<String>> {
+        let output_path = Self::get_path_for_contract_output(project, contract_info);
+        let contract_output = Self::get_contract_output(output_path)?;
+        let missing = &contract_output[contract_info.path.as_ref().unwrap()][&contract_info.name]
+            ["missingLibraries"];
+        Ok(missing
+            .as_array()
+            .map(|libraries| {
+                libraries
+                    .iter()
+                    .filter_map(|library| library.as_str().map(str::to_string))
+                    .collect()
+            })
+            .unwrap_or_default())
+    }
+
     /// This function retrieves the contract output.
     ///
     /// # Arguments
@@ -376,3 +725,280 @@ impl ZkCreateArgs {
         factory_deps
     }
 }
+
+/// Returns the missing libraries linked to the addresses of the libraries file, in the
+/// `<path>:<libraryname>:<address>` form of the `libraries` config.
+///
+/// Every missing library must have an address, either by its full name or by its library name,
+/// and every address must be of a missing library.
+fn library_links(
+    missing: &[String],
+    addresses: &BTreeMap<String, Address>,
+) -> eyre::Result<Vec<String>> {
+    let mut used = BTreeSet::new();
+    let mut links = Vec::with_capacity(missing.len());
+    for library in missing {
+        let name = library.rsplit(':').next().unwrap_or(library);
+        let (key, address) =
+            addresses.get_key_value(library).or_else(|| addresses.get_key_value(name)).ok_or_else(
+                || eyre::eyre!("The libraries file has no address for library {library}"),
+            )?;
+        used.insert(key.as_str());
+        links.push(format!("{library}:{address:?}"));
+    }
+    if let Some(unused) = addresses.keys().find(|key| !used.contains(key.as_str())) {
+        eyre::bail!("Library {unused} of the libraries file is not used by the contract")
+    }
+    Ok(links)
+}
+
+/// A deployment estimated with `--dry-run`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ZkDryRun {
+    /// The address the contract would be deployed to
+    pub address: Address,
+    pub bytecode_hash: H256,
+    /// The bytecode hashes of the factory dependencies sent along
+    pub factory_deps: Vec<H256>,
+    pub gas_limit: U256,
+    pub max_fee_per_gas: U256,
+    /// The estimated fee in wei, `gas_limit * max_fee_per_gas`
+    pub fee: U256,
+}
+
+fn print_dry_run(dry_run: &ZkDryRun) {
+    println!("+-------------------------------------------------+");
+    println!("Dry run, no transaction was sent");
+    println!("Predicted contract address: {:#?}", dry_run.address);
+    println!("Bytecode hash: {:#?}", dry_run.bytecode_hash);
+    for dep in &dry_run.factory_deps {
+        println!("Factory dependency: {dep:#?}");
+    }
+    println!("Estimated gas limit: {}", dry_run.gas_limit);
+    println!("Max fee per gas: {}", dry_run.max_fee_per_gas);
+    println!("Estimated fee: {} wei", dry_run.fee);
+    println!("+-------------------------------------------------+");
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{spawn_json_server, spawn_rpc_node};
+    use std::sync::mpsc;
+
+    /// Spawns a minimal zkSync explorer that accepts a verification submission, reports it as
+    /// successful when polled and forwards the submitted request body.
+    fn spawn_mock_explorer() -> (String, mpsc::Receiver<Value>) {
+        let (tx, rx) = mpsc::channel();
+        let url = spawn_json_server(move |body| match body {
+            Some(submission) => {
+                tx.send(submission).unwrap();
+                serde_json::json!(7)
+            }
+            None => serde_json::json!({ "status": "successful" }),
+        });
+        (format!("{url}/contract_verification"), rx)
+    }
+
+    /// Spawns a minimal zkSync node answering the calls of a dry run and forwarding the method of
+    /// every request it receives.
+    fn spawn_mock_node(deployment_nonce: u64) -> (String, mpsc::Receiver<String>) {
+        let (tx, rx) = mpsc::channel();
+        let url = spawn_rpc_node(move |method, _| {
+            tx.send(method.to_string()).unwrap();
+            match method {
+                "eth_call" => serde_json::json!(format!("0x{deployment_nonce:064x}")),
+                "zks_estimateFee" => serde_json::json!({
+                    "gas_limit": "0x2dc6c0",
+                    "max_fee_per_gas": "0xee6b280",
+                    "max_priority_fee_per_gas": "0x0",
+                    "gas_per_pubdata_limit": "0xc350"
+                }),
+                "eth_chainId" => serde_json::json!("0x118"),
+                _ => Value::Null,
+            }
+        });
+        (url, rx)
+    }
+
+    #[tokio::test]
+    async fn dry_run_predicts_address_without_sending() {
+        let (url, requests) = spawn_mock_node(5);
+        let args = ZkCreateArgs::parse_from([
+            "foundry-cli",
+            "src/Counter.sol:Counter",
+            "--dry-run",
+            "--rpc-url",
+            &url,
+        ]);
+        assert!(args.dry_run);
+
+        let private_key = "0x7726827caac94a7f9e1b160f7ea819f172f7b6f9d2a97f992c38edeab82d4110";
+        let bytecode = vec![0u8; 32 * 3];
+        let dep = vec![1u8; 32];
+        let dry_run = args.dry_run(private_key, &bytecode, &[dep.clone()], &[]).await.unwrap();
+
+        let sender = Address::from_str("0x36615Cf349d7F6344891B1e7CA7C72883F5dc049").unwrap();
+        assert_eq!(dry_run.address, compute_create_address(sender, 5.into()));
+        assert_ne!(dry_run.address, compute_create_address(sender, 4.into()));
+        assert_eq!(dry_run.bytecode_hash, H256(zk_bytecode_hash(&bytecode)));
+        assert_eq!(dry_run.factory_deps, vec![H256(zk_bytecode_hash(&dep))]);
+        assert_eq!(dry_run.fee, U256::from(3_000_000u64) * U256::from(250_000_000u64));
+
+        // only read requests reached the node
+        let methods: Vec<_> = requests.try_iter().collect();
+        assert!(methods.contains(&"eth_call".to_string()));
+        assert!(methods.contains(&"zks_estimateFee".to_string()));
+        assert!(methods.iter().all(|method| !method.starts_with("eth_send")), "{methods:?}");
+
+        assert!(ZkCreateArgs::try_parse_from([
+            "foundry-cli",
+            "src/Counter.sol:Counter",
+            "--dry-run",
+            "--verify"
+        ])
+        .is_err());
+    }
+
+    #[test]
+    fn links_libraries_to_the_mapped_addresses() {
+        let root = tempfile::tempdir().unwrap();
+        let artifacts = root.path().join("zkout").join("Counter.sol");
+        fs::create_dir_all(&artifacts).unwrap();
+        fs::write(
+            artifacts.join("artifacts.json"),
+            r#"{"contracts":{"src/Counter.sol":{"Counter":{
+                "missingLibraries":["src/Math.sol:Math","src/Strings.sol:Strings"]
+            }}},"compilerInfo":{"zksolcVersion":"1.3.9","systemMode":true,"forceEVMLA":false}}"#,
+        )
+        .unwrap();
+        let libraries_file = root.path().join("libraries.json");
+        fs::write(
+            &libraries_file,
+            r#"{
+                "src/Math.sol:Math": "0x36615cf349d7f6344891b1e7ca7c72883f5dc049",
+                "Strings": "0xa61464658afeaf65cccaafd3a512b69a83b77618"
+            }"#,
+        )
+        .unwrap();
+
+        let args = ZkCreateArgs::parse_from([
+            "foundry-cli",
+            "src/Counter.sol:Counter",
+            "--libraries-file",
+            libraries_file.to_str().unwrap(),
+            "--root",
+            root.path().to_str().unwrap(),
+        ]);
+        let mut project = args.opts.project().unwrap();
+        project.paths.artifacts = project.paths.root.join("zkout");
+        let (zk_build, config) =
+            args.relink_build(&project, args.libraries_file.as_ref().unwrap()).unwrap();
+
+        // the project is recompiled with the compilers and settings it was built with
+        assert_eq!(zk_build.use_zksolc, "v1.3.9");
+        assert!(zk_build.is_system);
+        assert!(!zk_build.force_evmla);
+
+        // the libraries zksolc is given target the mapped addresses
+        let libraries = config.parsed_libraries().unwrap();
+        assert_eq!(
+            libraries.libs[Path::new("src/Math.sol")]["Math"],
+            "0x36615cf349d7f6344891b1e7ca7c72883f5dc049"
+        );
+        assert_eq!(
+            libraries.libs[Path::new("src/Strings.sol")]["Strings"],
+            "0xa61464658afeaf65cccaafd3a512b69a83b77618"
+        );
+
+        // a forced legacy assembly pipeline the config no longer reproduces is an error
+        fs::write(
+            root.path().join("foundry.toml"),
+            "[profile.default]\n[profile.default.zksolc.contracts.\"src/Counter.sol\"]\nforce_evmla = false\n",
+        )
+        .unwrap();
+        fs::write(
+            artifacts.join("artifacts.json"),
+            r#"{"contracts":{"src/Counter.sol":{"Counter":{
+                "missingLibraries":["src/Math.sol:Math","src/Strings.sol:Strings"]
+            }}},"compilerInfo":{"forceEVMLA":true}}"#,
+        )
+        .unwrap();
+        let err = args.relink_build(&project, args.libraries_file.as_ref().unwrap()).unwrap_err();
+        assert!(err.to_string().contains("forceEVMLA = true"), "{err}");
+    }
+
+    #[test]
+    fn mismatched_libraries_are_named() {
+        let missing = vec!["src/Math.sol:Math".to_string()];
+        let address = Address::from_str("0x36615Cf349d7F6344891B1e7CA7C72883F5dc049").unwrap();
+
+        let err = library_links(&missing, &BTreeMap::new()).unwrap_err();
+        assert!(err.to_string().contains("no address for library src/Math.sol:Math"));
+
+        let addresses =
+            BTreeMap::from([("Math".to_string(), address), ("Strings".to_string(), address)]);
+        let err = library_links(&missing, &addresses).unwrap_err();
+        assert!(err.to_string().contains("Library Strings"));
+    }
+
+    #[test]
+    fn can_parse_zk_create_verify() {
+        let args = ZkCreateArgs::parse_from([
+            "foundry-cli",
+            "src/Counter.sol:Counter",
+            "--verify",
+            "--verifier",
+            "zksync",
+            "--verifier-url",
+            "https://explorer.example/contract_verification",
+        ]);
+        assert!(args.verify);
+        assert_eq!(args.verifier.verifier, verify::provider::VerificationProviderType::ZkSync);
+    }
+
+    #[tokio::test]
+    async fn submits_deployed_address_for_verification() {
+        let root = tempfile::tempdir().unwrap();
+        let artifacts = root.path().join("zkout").join("Counter.sol");
+        fs::create_dir_all(&artifacts).unwrap();
+        fs::write(
+            artifacts.join("json_input.json"),
+            r#"{"language":"Solidity","sources":{},"settings":{"optimizer":{"enabled":true}}}"#,
+        )
+        .unwrap();
+        fs::write(
+            artifacts.join("artifacts.json"),
+            r#"{"contracts":{},"version":"0.8.19","zk_version":"1.3.11"}"#,
+        )
+        .unwrap();
+
+        let (url, submissions) = spawn_mock_explorer();
+        let args = ZkCreateArgs::parse_from([
+            "foundry-cli",
+            "src/Counter.sol:Counter",
+            "--verify",
+            "--verifier",
+            "zksync",
+            "--verifier-url",
+            &url,
+            "--root",
+            root.path().to_str().unwrap(),
+            "--retries",
+            "1",
+            "--delay",
+            "0",
+        ]);
+
+        let address = Address::from_str("0x36615Cf349d7F6344891B1e7CA7C72883F5dc049").unwrap();
+        let verify = args.verify_args(address, None, 280);
+        verify.verification_provider().unwrap().verify(verify).await.unwrap();
+
+        let submission = submissions.recv().unwrap();
+        assert_eq!(submission["contractAddress"], format!("{address:?}"));
+        assert_eq!(submission["contractName"], "src/Counter.sol:Counter");
+        assert_eq!(submission["compilerZksolcVersion"], "v1.3.11");
+        assert_eq!(submission["compilerSolcVersion"], "0.8.19");
+        assert_eq!(submission["sourceCode"]["settings"]["optimizer"]["enabled"], true);
+    }
+}
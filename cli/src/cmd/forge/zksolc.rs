@@ -8,8 +8,8 @@
 ///
 /// The `ZkSolc` struct provides the following functionality:
 ///
-/// - Configuration: It allows configuring the compiler path, system mode, and force-evmla
-///   options through the `ZkSolcOpts` struct.
+/// - Configuration: It allows configuring the compiler path, system mode, and the `zksolc`
+///   config section (e.g. force-evmla) through the `ZkSolcOpts` struct.
 ///
 /// - Compilation: The `compile` method initiates the compilation process. It collects the
 ///   source files, parses the JSON input, builds compiler arguments, runs the compiler, and
@@ -19,6 +19,9 @@
 ///   they are displayed appropriately. If errors are encountered, the process will exit with a
 ///   non-zero status code.
 ///
+/// - Yul Objects: Standalone `.yul` sources are compiled in zksolc's Yul mode, and their
+///   bytecode is saved in the same artifacts format as the Solidity contracts.
+///
 /// - JSON Input Generation: The `parse_json_input` method generates the JSON input required by
 ///   the compiler for each contract. It configures the Solidity compiler, saves the input to
 ///   the artifacts directory, and handles the output.
@@ -33,27 +36,196 @@ use ansi_term::Colour::{Red, Yellow};
 use anyhow::{Error, Result};
 use ethers::{
     prelude::{artifacts::Source, Solc},
-    solc::{
-        artifacts::{output_selection::FileOutputSelection, StandardJsonCompilerInput},
-        Graph, Project,
-    },
+    solc::{artifacts::output_selection::FileOutputSelection, EvmVersion, Graph, Project},
+    utils::keccak256,
 };
+use foundry_config::ZkSolcConfig;
 use semver::Version;
+use serde::{Deserialize, Serialize};
 use serde_json::Value;
+use sha2::{Digest, Sha256};
 use std::{
-    collections::{BTreeMap, HashSet},
+    collections::{BTreeMap, BTreeSet, HashSet},
     fmt, fs,
     fs::File,
     io::Write,
-    path::PathBuf,
-    process::{exit, Command, Stdio},
+    path::{Path, PathBuf},
+    process::{exit, Command, Output, Stdio},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    thread,
 };
 
+/// The contract level outputs that can be requested from zksolc via `zksolc.output_selection`.
+pub const ZKSOLC_SUPPORTED_OUTPUTS: &[&str] = &[
+    "abi",
+    "metadata",
+    "devdoc",
+    "userdoc",
+    "storageLayout",
+    "irOptimized",
+    "evm.methodIdentifiers",
+    "evm.legacyAssembly",
+    "evm.assembly",
+    "evm.bytecode",
+];
+
+/// The EVM versions the zksolc frontend can target via `zksolc.evm_version`.
+pub const ZKSOLC_SUPPORTED_EVM_VERSIONS: &[EvmVersion] = &[
+    EvmVersion::Homestead,
+    EvmVersion::TangerineWhistle,
+    EvmVersion::SpuriousDragon,
+    EvmVersion::Byzantium,
+    EvmVersion::Constantinople,
+    EvmVersion::Petersburg,
+    EvmVersion::Istanbul,
+    EvmVersion::Berlin,
+    EvmVersion::London,
+    EvmVersion::Paris,
+];
+
+/// The first zksolc version taking `settings.enableEraVMExtensions`, older ones enable the EraVM
+/// extensions in system mode.
+pub const ZKSOLC_ERAVM_EXTENSIONS_VERSION: Version = Version::new(1, 5, 0);
+
+/// The LLVM optimization modes zksolc supports via `zksolc.optimizer_mode`.
+pub const ZKSOLC_OPTIMIZER_MODES: &[&str] = &["0", "1", "2", "3", "s", "z"];
+
 #[derive(Debug, Clone)]
 pub struct ZkSolcOpts {
     pub compiler_path: PathBuf,
     pub is_system: bool,
-    pub force_evmla: bool,
+    pub settings: ZkSolcConfig,
+    pub format_json: bool,
+    pub skip_solc_version_check: bool,
+    /// The maximum number of zksolc processes to run concurrently
+    pub jobs: usize,
+    /// Whether to recompile every source regardless of the cache, e.g. with `--force`
+    pub force: bool,
+    /// Whether the compilation units are cached in [`ZKSOLC_CACHE_FILENAME`], see `cache`
+    pub cache: bool,
+}
+
+/// The oldest solc version zksolc supports
+pub const MIN_SUPPORTED_SOLC_VERSION: Version = Version::new(0, 4, 12);
+
+/// The newest solc version supported by zksolc releases, as `(zksolc, solc)` pairs: every zksolc
+/// version from the listed one up to the next entry supports solc up to the listed version. The last
+/// entry only covers its own release, newer zksolc releases aren't checked.
+const ZKSOLC_MAX_SOLC_VERSIONS: &[((u64, u64, u64), (u64, u64, u64))] = &[
+    ((1, 3, 5), (0, 8, 17)),
+    ((1, 3, 6), (0, 8, 18)),
+    ((1, 3, 7), (0, 8, 19)),
+    ((1, 3, 11), (0, 8, 20)),
+];
+
+/// The maximum size of a contract's bytecode zkSync can deploy.
+///
+/// The bytecode hash encodes the length in 32 byte words in two bytes, and the length must be odd.
+/// On zkSync the constructor is part of the same bytecode, so this also limits the init code.
+pub const ZKSYNC_MAX_BYTECODE_SIZE: usize = (u16::MAX as usize) * 32;
+
+/// The file in the artifacts directory recording the compilation units of the last build
+pub const ZKSOLC_CACHE_FILENAME: &str = "zksolc-cache.json";
+
+/// The field of `artifacts.json` recording the compilers and settings that produced it
+pub const ZKSOLC_COMPILER_INFO_KEY: &str = "compilerInfo";
+
+/// A zksolc error or warning, as reported with `--format-json`
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ZkSolcDiagnostic {
+    pub severity: String,
+    pub code: Option<String>,
+    pub message: String,
+    pub source_location: Option<ZkSolcSourceLocation>,
+    /// The source file that was being compiled when the diagnostic was reported
+    pub contract: String,
+}
+
+/// The location of a [`ZkSolcDiagnostic`] in a source file
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ZkSolcSourceLocation {
+    pub file: String,
+    pub start: i64,
+    pub end: i64,
+}
+
+/// A source file zksolc is run for
+#[derive(Debug, Clone)]
+struct ZkSolcJob {
+    contract_path: PathBuf,
+    /// The file name of the source, naming its artifacts directory
+    filename: String,
+    /// The source path in the cache
+    cache_source: String,
+    cache_key: String,
+    args: Vec<String>,
+    standard_json: Value,
+    /// Recorded in the artifacts as [`ZKSOLC_COMPILER_INFO_KEY`]
+    compiler_info: Value,
+    /// The name of the top-level object of a standalone Yul source, which is compiled in Yul
+    /// mode instead of from the standard json
+    yul_object: Option<String>,
+}
+
+impl ZkSolcJob {
+    /// Runs zksolc for the source, failing if the process doesn't exit successfully
+    fn run(&self, compiler_path: &Path) -> Result<Output> {
+        let mut child = Command::new(compiler_path)
+            .arg(&self.contract_path)
+            .args(&self.args)
+            .stdin(Stdio::piped())
+            .stderr(Stdio::piped())
+            .stdout(Stdio::piped())
+            .spawn()
+            .map_err(|e| Error::msg(format!("Could not run compiler cmd: {}", e)))?;
+        let stdin = child.stdin.take().expect("Stdin exists.");
+
+        // in Yul mode the source is only read from its path
+        if self.yul_object.is_none() {
+            serde_json::to_writer(stdin, &self.standard_json).map_err(|e| {
+                Error::msg(format!("Could not assign standard_json to writer: {}", e))
+            })?;
+        } else {
+            drop(stdin);
+        }
+
+        let output = child
+            .wait_with_output()
+            .map_err(|e| Error::msg(format!("Could not run compiler cmd: {}", e)))?;
+
+        if !output.status.success() {
+            return Err(Error::msg(format!(
+                "Compilation failed with {:?}. Using compiler: {:?}, with args {:?} {:?}",
+                String::from_utf8(output.stderr).unwrap_or_default(),
+                compiler_path,
+                self.contract_path,
+                &self.args
+            )))
+        }
+        Ok(output)
+    }
+}
+
+/// Runs the given jobs on up to `workers` threads, returning their outputs in the order of the
+/// jobs.
+fn run_jobs(compiler_path: &Path, jobs: &[ZkSolcJob], workers: usize) -> Vec<Result<Output>> {
+    let next = AtomicUsize::new(0);
+    let outputs = Mutex::new((0..jobs.len()).map(|_| None).collect::<Vec<_>>());
+    thread::scope(|scope| {
+        for _ in 0..workers.clamp(1, jobs.len().max(1)) {
+            scope.spawn(|| loop {
+                let idx = next.fetch_add(1, Ordering::Relaxed);
+                let Some(job) = jobs.get(idx) else { break };
+                let output = job.run(compiler_path);
+                outputs.lock().unwrap()[idx] = Some(output);
+            });
+        }
+    });
+    outputs.into_inner().unwrap().into_iter().map(|output| output.expect("job was run")).collect()
 }
 
 /// Files that should be compiled with a given solidity version.
@@ -70,7 +242,14 @@ type SolidityVersionSources = (Version, BTreeMap<PathBuf, Source>);
 /// - `project`: Represents the project details and configurations.
 /// - `compiler_path`: The path to the ZkSolc compiler executable.
 /// - `is_system`: A flag indicating whether the compiler is in system mode.
-/// - `force_evmla`: A flag indicating whether to force EVMLA optimization.
+/// - `settings`: The `zksolc` section of the config, e.g. the output selection and whether to
+///   force the EVM legacy assembly pipeline, globally or for individual source files.
+/// - `format_json`: A flag indicating whether errors and warnings are reported as JSON.
+/// - `skip_solc_version_check`: A flag to compile even if zksolc doesn't support the solc version.
+/// - `jobs`: The maximum number of zksolc processes to run concurrently.
+/// - `force`: A flag to recompile every source, ignoring the cache.
+/// - `cache`: A flag indicating whether the compilation units are cached across builds.
+/// - `diagnostics`: The errors and warnings collected for the JSON report.
 /// - `standard_json`: An optional field to store the parsed standard JSON input for the contracts.
 /// - `sources`: An optional field to store the versioned sources for the contracts.
 ///
@@ -111,8 +290,14 @@ pub struct ZkSolc {
     project: Project,
     compiler_path: PathBuf,
     is_system: bool,
-    force_evmla: bool,
-    standard_json: Option<StandardJsonCompilerInput>,
+    settings: ZkSolcConfig,
+    format_json: bool,
+    skip_solc_version_check: bool,
+    jobs: usize,
+    force: bool,
+    cache: bool,
+    diagnostics: Vec<ZkSolcDiagnostic>,
+    standard_json: Option<Value>,
     sources: Option<BTreeMap<Solc, SolidityVersionSources>>,
 }
 
@@ -136,7 +321,13 @@ impl ZkSolc {
             project,
             compiler_path: opts.compiler_path,
             is_system: opts.is_system,
-            force_evmla: opts.force_evmla,
+            settings: opts.settings,
+            format_json: opts.format_json,
+            skip_solc_version_check: opts.skip_solc_version_check,
+            jobs: opts.jobs,
+            force: opts.force,
+            cache: opts.cache,
+            diagnostics: Vec::new(),
             standard_json: None,
             sources: None,
         }
@@ -152,6 +343,10 @@ impl ZkSolc {
     /// # Errors
     ///
     /// This function can return an error if any of the following occurs:
+    /// - The configured output selection contains an output zksolc does not support.
+    /// - The configured EVM version is not supported by zksolc.
+    /// - The configured optimizer mode is not supported by zksolc.
+    /// - zksolc doesn't support the version of a solc compiler the sources require.
     /// - The Solidity compiler fails to execute or encounters an error during compilation.
     /// - The source files cannot be collected from the project's 'sources' directory.
     /// - The compiler arguments cannot be built.
@@ -164,7 +359,12 @@ impl ZkSolc {
     /// let opts = ZkSolcOpts {
     ///     compiler_path: PathBuf::from("/path/to/zksolc"),
     ///     is_system: false,
-    ///     force_evmla: true,
+    ///     settings: ZkSolcConfig { force_evmla: true, ..Default::default() },
+    ///     format_json: false,
+    ///     skip_solc_version_check: false,
+    ///     jobs: 4,
+    ///     force: false,
+    ///     cache: true,
     /// };
     /// let mut zksolc = ZkSolc::new(opts, project);
     /// zksolc.compile()?;
@@ -189,21 +389,30 @@ impl ZkSolc {
     ///
     /// 3. Parse JSON Input:
     ///    - For each source file, it parses the JSON input using the Solidity compiler.
+    ///    - The zksolc specific settings of the source file, like `forceEVMLA`, are applied.
     ///    - The parsed JSON input is stored in the `standard_json` field of the `ZkSolc` instance.
+    ///    - If caching is enabled and the JSON input, which contains the source file and all of
+    ///      its imports, is unchanged since the last build, the source is not recompiled and its
+    ///      artifacts are kept. Changing a file thus recompiles it and every file importing it.
+    ///      With `force`, every source is recompiled.
     ///
     /// 4. Build Compiler Arguments:
     ///    - It builds the compiler arguments for each source file.
-    ///    - The compiler arguments include options like the compiler path, system mode, and force
-    ///      EVMLA flag.
+    ///    - The compiler arguments include options like the compiler path and system mode.
     ///
     /// 5. Run Compiler and Handle Output:
     ///    - It runs the Solidity compiler for each source file with the corresponding compiler
-    ///      arguments.
+    ///      arguments, running up to `jobs` compiler processes concurrently.
     ///    - The output of the compiler, including errors and warnings, is captured.
+    ///    - Outputs are handled in the order of the sources, regardless of which process finishes
+    ///      first. A source the compiler fails on doesn't stop the others from being compiled, all
+    ///      failures are reported once every source has been handled.
     ///
     /// 6. Handle Output (Errors and Warnings):
     ///    - It handles the output of the compiler, extracting errors and warnings.
     ///    - Errors are printed in red, and warnings are printed in yellow.
+    ///    - With `format_json`, they are instead collected and printed as a single JSON array once
+    ///      all sources are compiled, along with the sources the compiler failed on.
     ///
     /// 7. Save Artifacts:
     ///    - It saves the artifacts (compiler output) as a JSON file for each source file.
@@ -216,11 +425,41 @@ impl ZkSolc {
     /// versioned sources. These modified values can be accessed after the compilation process
     /// for further processing or analysis.
     pub fn compile(mut self) -> Result<()> {
+        validate_output_selection(&self.settings.output_selection)?;
+        if let Some(evm_version) = self.settings.evm_version {
+            validate_evm_version(evm_version)?;
+        }
+        if let Some(mode) = &self.settings.optimizer_mode {
+            validate_optimizer_mode(mode)?;
+        }
+
         // Step 1: Collect Source Files
         self.configure_solc();
         let sources = self.sources.clone().unwrap();
         let mut displayed_warnings = HashSet::new();
 
+        // Abort before compiling anything if zksolc doesn't support a required solc version
+        let zksolc_version = match zksolc_version(&self.compiler_path) {
+            Ok(version) => Some(version),
+            // the version is then only missing from the recorded compiler info
+            Err(_) if self.skip_solc_version_check => None,
+            Err(err) => return Err(err),
+        };
+        if let (false, Some(zksolc_version)) = (self.skip_solc_version_check, &zksolc_version) {
+            for solc in sources.keys() {
+                let solc_version = solc
+                    .version()
+                    .map_err(|e| Error::msg(format!("Could not get the solc version: {}", e)))?;
+                check_solc_compatibility(zksolc_version, &solc_version)?;
+            }
+        }
+
+        let cache_path = self.project.paths.artifacts.join(ZKSOLC_CACHE_FILENAME);
+        let cache = self.read_cache(&cache_path);
+        let mut new_cache = ZkSolcCache::default();
+        let mut skipped = 0;
+        let mut jobs = Vec::new();
+
         // Step 2: Compile Contracts for Each Source
         for (solc, version) in sources {
             //configure project solc for each solc version
@@ -237,42 +476,66 @@ impl ZkSolc {
                     continue
                 }
 
-                // Step 3: Parse JSON Input for each Source
-                if let Err(err) = self.parse_json_input(contract_path.clone()) {
-                    eprintln!("Failed to parse json input for zksolc compiler: {}", err);
+                // Step 3: Parse JSON Input for each Source, standalone Yul objects have none
+                let yul_object = if is_yul_source(&contract_path) {
+                    let name = yul_object_name(&source.1.content).ok_or_else(|| {
+                        Error::msg(format!("No Yul object found in {}", contract_path.display()))
+                    })?;
+                    // the factory dependencies can't be recovered from the printed bytecode
+                    let dependencies = yul_factory_dependencies(&source.1.content, name);
+                    if !dependencies.is_empty() {
+                        return Err(Error::msg(format!(
+                            "{} deploys the nested Yul objects {}, which zksolc doesn't report as \
+                             factory dependencies in Yul mode. Deploy them from a Solidity contract \
+                             instead",
+                            contract_path.display(),
+                            dependencies.join(", ")
+                        )))
+                    }
+                    self.standard_json = Some(serde_json::json!({
+                        "language": "Yul",
+                        "source": source.1.content.as_str(),
+                    }));
+                    Some(name.to_string())
+                } else {
+                    self.parse_json_input(contract_path.clone()).map_err(|err| {
+                        Error::msg(format!("Failed to parse json input for zksolc compiler: {err}"))
+                    })?;
+                    None
+                };
+
+                // Skip the source if its compilation unit didn't change since the last build
+                let cache_source = contract_path
+                    .strip_prefix(&self.project.paths.root)
+                    .unwrap_or(&contract_path)
+                    .to_string_lossy()
+                    .to_string();
+                let fingerprint = format!(
+                    "{:?}:{:?}:{}:{}",
+                    self.compiler_path,
+                    solc.solc,
+                    self.is_system,
+                    serde_json::to_string(&self.settings).unwrap_or_default()
+                );
+                let cache_key = unit_cache_key(self.standard_json.as_ref().unwrap(), &fingerprint);
+                let artifacts_file =
+                    zk_artifacts_dir(&self.project.paths.artifacts, &contract_path)
+                        .expect("Failed to get Contract filename.")
+                        .join("artifacts.json");
+                // artifacts of older builds are recompiled to record their compiler info
+                if cache.is_fresh(&cache_source, &cache_key) && has_compiler_info(&artifacts_file) {
+                    new_cache.sources.insert(cache_source, cache_key);
+                    skipped += 1;
+                    continue
                 }
 
                 // Step 4: Build Compiler Arguments
-                let comp_args = self.build_compiler_args(source.clone(), solc.clone());
-
-                // Step 5: Run Compiler and Handle Output
-                let mut cmd = Command::new(&self.compiler_path);
-                let mut child = cmd
-                    .arg(contract_path.clone())
-                    .args(&comp_args)
-                    .stdin(Stdio::piped())
-                    .stderr(Stdio::piped())
-                    .stdout(Stdio::piped())
-                    .spawn();
-                let stdin = child.as_mut().unwrap().stdin.take().expect("Stdin exists.");
-
-                serde_json::to_writer(stdin, &self.standard_json.clone().unwrap()).map_err(
-                    |e| Error::msg(format!("Could not assign standard_json to writer: {}", e)),
-                )?;
-
-                let output = child
-                    .unwrap()
-                    .wait_with_output()
-                    .map_err(|e| Error::msg(format!("Could not run compiler cmd: {}", e)))?;
-
-                if !output.status.success() {
-                    return Err(Error::msg(format!(
-                        "Compilation failed with {:?}. Using compiler: {:?}, with args {:?} {:?}",
-                        String::from_utf8(output.stderr).unwrap_or_default(),
-                        self.compiler_path,
-                        contract_path,
-                        &comp_args
-                    )))
+                let mut args = self.build_compiler_args(source.clone(), solc.clone());
+                if self.settings.enable_eravm_extensions &&
+                    needs_system_mode_for_extensions(zksolc_version.as_ref())
+                {
+                    let standard_json = self.standard_json.as_mut().unwrap();
+                    enable_eravm_extensions_with_system_mode(standard_json, &mut args);
                 }
 
                 let filename = contract_path
@@ -289,12 +552,89 @@ impl ZkSolc {
                     .expect("Failed to get Contract relative path")
                     .split('/')
                     .last()
-                    .expect("Failed to get Contract filename.");
+                    .expect("Failed to get Contract filename.")
+                    .to_string();
+
+                let standard_json = self.standard_json.clone().unwrap();
+                let compiler_info = compiler_info(
+                    &standard_json,
+                    zksolc_version.as_ref(),
+                    &version.0,
+                    args.iter().any(|arg| arg == "--system-mode"),
+                    self.settings.append_cbor,
+                );
+                jobs.push(ZkSolcJob {
+                    contract_path,
+                    filename,
+                    cache_source,
+                    cache_key,
+                    args,
+                    standard_json,
+                    compiler_info,
+                    yul_object,
+                });
+            }
+        }
+
+        // Step 5: Run the Compiler for all Sources, `self.jobs` at a time
+        let outputs = run_jobs(&self.compiler_path, &jobs, self.jobs);
+
+        // Step 6: Handle Output (Errors and Warnings), in the order of the sources
+        let mut failures = Vec::new();
+        for (job, output) in jobs.into_iter().zip(outputs) {
+            // Yul mode only prints the bytecode, which is saved like the standard json output
+            let output = match (output, &job.yul_object) {
+                (Ok(mut output), Some(name)) => yul_output_json(
+                    &String::from_utf8_lossy(&output.stdout),
+                    &job.cache_source,
+                    name,
+                )
+                .map(|json| {
+                    output.stdout = json.to_string().into_bytes();
+                    output
+                }),
+                (output, _) => output,
+            };
+            match output {
+                Ok(output) => {
+                    if self.handle_output(
+                        output,
+                        job.filename,
+                        job.compiler_info,
+                        &mut displayed_warnings,
+                    ) {
+                        new_cache.sources.insert(job.cache_source, job.cache_key);
+                    }
+                }
+                Err(err) if self.format_json => self.diagnostics.push(ZkSolcDiagnostic {
+                    severity: "error".to_string(),
+                    code: None,
+                    message: err.to_string(),
+                    source_location: None,
+                    contract: job.filename,
+                }),
+                Err(err) => failures.push(err.to_string()),
+            }
+        }
 
-                // Step 6: Handle Output (Errors and Warnings)
-                self.handle_output(output, filename.to_string(), &mut displayed_warnings);
+        if self.cache {
+            new_cache.write(&cache_path)?;
+        }
+        if skipped > 0 && !self.format_json {
+            println!("Skipped {skipped} unchanged source file(s)");
+        }
+        if self.format_json {
+            let report = serde_json::to_string_pretty(&self.diagnostics)
+                .map_err(|e| Error::msg(format!("Could not serialize diagnostics: {}", e)))?;
+            println!("{report}");
+            if self.diagnostics.iter().any(|d| !d.severity.eq_ignore_ascii_case("warning")) {
+                exit(1);
             }
         }
+        // the artifacts of every other source are saved, so only the failed ones are recompiled
+        if !failures.is_empty() {
+            return Err(Error::msg(failures.join("\n")))
+        }
 
         // Step 7: Return Ok if the compilation process completes without errors
         Ok(())
@@ -325,21 +665,23 @@ impl ZkSolc {
             .unwrap_or_else(|| panic!("Error configuring solc compiler."))
             .to_string();
 
-        // Build compiler arguments
+        // Build compiler arguments, standalone Yul objects are compiled in Yul mode
+        let is_yul = is_yul_source(&versioned_source.0);
         let mut comp_args = Vec::<String>::new();
-        comp_args.push("--standard-json".to_string());
+        comp_args.push(if is_yul { "--yul" } else { "--standard-json" }.to_string());
         comp_args.push("--solc".to_string());
         comp_args.push(solc_path.to_owned());
+        if is_yul {
+            comp_args.push("--bin".to_string());
+        } else {
+            comp_args.extend(metadata_hash_args(&self.settings));
+        }
 
         // Check if system mode is enabled or if the source path contains "is-system"
         if self.is_system || versioned_source.0.to_str().unwrap().contains("is-system") {
             comp_args.push("--system-mode".to_string());
         }
 
-        // Check if force-evmla is enabled
-        if self.force_evmla {
-            comp_args.push("--force-evmla".to_string());
-        }
         comp_args
     }
 
@@ -351,6 +693,7 @@ impl ZkSolc {
     ///
     /// * `output` - The output of the Solidity compiler as a `std::process::Output` struct.
     /// * `source` - The path of the contract source file that was compiled.
+    /// * `compiler_info` - The compilers and settings used, recorded in the artifacts.
     /// * `displayed_warnings` - A mutable set that keeps track of displayed warnings to avoid
     ///   duplicates.
     ///
@@ -380,24 +723,88 @@ impl ZkSolc {
     /// let output = std::process::Output { ... };
     /// let source = "/path/to/contract.sol".to_string();
     /// let mut displayed_warnings = HashSet::new();
-    /// self.handle_output(output, source, &mut displayed_warnings);
+    /// self.handle_output(output, source, compiler_info, &mut displayed_warnings);
     /// ```
     ///
     /// In this example, the `handle_output` function is called with the compiler output, contract
     /// source, and a mutable set for displayed warnings. It processes the output, handles
     /// errors and warnings, and saves the artifacts.
+    ///
+    /// Returns whether the artifacts were saved.
     fn handle_output(
-        &self,
+        &mut self,
         output: std::process::Output,
         source: String,
+        compiler_info: Value,
         displayed_warnings: &mut HashSet<String>,
-    ) {
+    ) -> bool {
         // Deserialize the compiler output into a serde_json::Value object
-        let output_json: Value = serde_json::from_slice(&output.clone().stdout)
+        let mut output_json: Value = serde_json::from_slice(&output.clone().stdout)
             .unwrap_or_else(|e| panic!("Could not parse zksolc compiler output: {}", e));
 
         // Handle errors and warnings in the output
-        self.handle_output_errors(&output_json, displayed_warnings);
+        if self.format_json {
+            let has_error = self.collect_diagnostics(&output_json, &source, displayed_warnings);
+            // there are no artifacts to save for a source that failed to compile
+            if has_error {
+                return false
+            }
+        } else {
+            self.handle_output_errors(&output_json, displayed_warnings);
+        }
+
+        // Fail instead of saving incomplete artifacts if libraries are left unlinked
+        if self.settings.fail_on_missing_libraries {
+            let missing = missing_libraries(&output_json);
+            if !missing.is_empty() {
+                if !self.format_json {
+                    for library in &missing {
+                        println!("{}", Red.paint(format!("Error: Missing library {library}")));
+                    }
+                    exit(1);
+                }
+                self.diagnostics.extend(missing.into_iter().map(|library| ZkSolcDiagnostic {
+                    severity: "error".to_string(),
+                    code: None,
+                    message: format!("Missing library {library}"),
+                    source_location: None,
+                    contract: source.clone(),
+                }));
+                return false
+            }
+        }
+
+        // Flag contracts that are too large to be deployed
+        let oversized = oversized_contracts(&output_json);
+        if !oversized.is_empty() {
+            let fail = self.settings.fail_on_bytecode_size_limit;
+            for (contract, size) in oversized {
+                let message = format!(
+                    "{contract} is {size} bytes, exceeding the zkSync bytecode size limit of {} bytes by {} bytes",
+                    ZKSYNC_MAX_BYTECODE_SIZE,
+                    size - ZKSYNC_MAX_BYTECODE_SIZE
+                );
+                if self.format_json {
+                    self.diagnostics.push(ZkSolcDiagnostic {
+                        severity: if fail { "error" } else { "warning" }.to_string(),
+                        code: None,
+                        message,
+                        source_location: None,
+                        contract: source.clone(),
+                    });
+                } else if fail {
+                    println!("{}", Red.paint(format!("Error: {message}")));
+                } else {
+                    println!("{}", Yellow.paint(format!("Warning: {message}")));
+                }
+            }
+            if fail {
+                if !self.format_json {
+                    exit(1);
+                }
+                return false
+            }
+        }
 
         // Create the artifacts file for saving the compiler output
         let mut artifacts_file = self
@@ -413,12 +820,19 @@ impl ZkSolc {
                 let b_code_keys = b_code_obj.keys();
                 for hash in b_code_keys {
                     if let Some(bcode_hash) = b_code_obj[hash]["hash"].as_str() {
+                        if self.format_json {
+                            continue
+                        }
                         println!("{} -> Bytecode Hash: {} ", hash, bcode_hash);
                     }
                 }
             }
         }
 
+        // Drop the outputs zksolc always emits but which were not requested
+        retain_selected_outputs(&mut output_json, &self.settings.output_selection);
+        output_json[ZKSOLC_COMPILER_INFO_KEY] = compiler_info;
+
         // Beautify the output JSON
         let output_json_pretty = serde_json::to_string_pretty(&output_json)
             .unwrap_or_else(|e| panic!("Could not beautify zksolc compiler output: {}", e));
@@ -427,6 +841,7 @@ impl ZkSolc {
         artifacts_file
             .write_all(output_json_pretty.as_bytes())
             .unwrap_or_else(|e| panic!("Could not write artifacts file: {}", e));
+        true
     }
 
     /// Handles the errors and warnings present in the output JSON from the compiler.
@@ -487,6 +902,30 @@ impl ZkSolc {
         }
     }
 
+    /// Collects the errors and warnings present in the output JSON from the compiler for the JSON
+    /// report, skipping diagnostics that were already reported for a previously compiled source.
+    ///
+    /// Returns whether the output contains an error.
+    fn collect_diagnostics(
+        &mut self,
+        output_json: &Value,
+        source: &str,
+        reported: &mut HashSet<String>,
+    ) -> bool {
+        let diagnostics = parse_diagnostics(output_json, source);
+        let has_error = diagnostics.iter().any(|d| !d.severity.eq_ignore_ascii_case("warning"));
+        for diagnostic in diagnostics {
+            let key = format!(
+                "{}:{}:{:?}",
+                diagnostic.severity, diagnostic.message, diagnostic.source_location
+            );
+            if reported.insert(key) {
+                self.diagnostics.push(diagnostic);
+            }
+        }
+        has_error
+    }
+
     /// Parses the JSON input for a contract and prepares the necessary configuration for the ZkSolc
     /// compiler.
     ///
@@ -507,7 +946,7 @@ impl ZkSolc {
     ///
     /// 1. Configure File Output Selection:
     ///    - It configures the file output selection to specify which outputs should be included in
-    ///      the compiler output.
+    ///      the compiler output, based on the configured `zksolc.output_selection`.
     ///
     /// 2. Configure Solidity Compiler:
     ///    - It modifies the Solidity compiler settings to exclude metadata from the output.
@@ -543,14 +982,16 @@ impl ZkSolc {
     /// the input to the artifacts directory.
     fn parse_json_input(&mut self, contract_path: PathBuf) -> Result<()> {
         // Step 1: Configure File Output Selection
+        // zksolc always emits the bytecode, so it is not forwarded to solc
         let mut file_output_selection: FileOutputSelection = BTreeMap::default();
         file_output_selection.insert(
             "*".to_string(),
-            vec![
-                "abi".to_string(),
-                "evm.methodIdentifiers".to_string(),
-                // "evm.legacyAssembly".to_string(),
-            ],
+            self.settings
+                .output_selection
+                .iter()
+                .filter(|output| output.as_str() != "evm.bytecode")
+                .cloned()
+                .collect(),
         );
         file_output_selection.insert(
             "".to_string(),
@@ -577,14 +1018,22 @@ impl ZkSolc {
             .insert("*".to_string(), file_output_selection.clone());
 
         // Step 4: Generate Standard JSON Input
+        // The imports are resolved with the remappings of the project, the same ones `forge build`
+        // uses, including auto-detected ones like `forge-std`
         let standard_json = self
             .project
             .standard_json_input(&contract_path)
-            .map_err(|e| Error::msg(format!("Could not get standard json input: {}", e)))
-            .unwrap();
+            .map_err(|e| Error::msg(format!("Could not get standard json input: {}", e)))?;
+
+        // Apply the zksolc specific settings of this source file
+        let mut standard_json = serde_json::to_value(&standard_json)
+            .map_err(|e| Error::msg(format!("Could not serialize standard JSON input: {}", e)))?;
+        let relative_path =
+            contract_path.strip_prefix(&self.project.paths.root).unwrap_or(&contract_path);
+        apply_zksolc_settings(&mut standard_json, &self.settings, relative_path);
 
         // Store the generated standard JSON input in the ZkSolc instance
-        self.standard_json = Some(standard_json.to_owned());
+        self.standard_json = Some(standard_json.clone());
 
         // Step 5: Build Artifacts Path
         let artifact_path = &self
@@ -594,9 +1043,7 @@ impl ZkSolc {
 
         // Step 6: Save JSON Input
         let json_input_path = artifact_path.join("json_input.json");
-        let stdjson = serde_json::to_value(&standard_json)
-            .map_err(|e| Error::msg(format!("Could not serialize standard JSON input: {}", e)))?;
-        std::fs::write(json_input_path, serde_json::to_string_pretty(&stdjson).unwrap())
+        std::fs::write(json_input_path, serde_json::to_string_pretty(&standard_json).unwrap())
             .map_err(|e| Error::msg(format!("Could not write JSON input file: {}", e)))?;
 
         Ok(())
@@ -734,13 +1181,22 @@ impl ZkSolc {
     /// - The extraction of the filename from the contract source path fails.
     /// - The creation of the artifacts directory fails.
     fn build_artifacts_path(&self, source: PathBuf) -> Result<PathBuf, anyhow::Error> {
-        let filename = source.file_name().expect("Failed to get Contract filename.");
-        let path = self.project.paths.artifacts.join(filename);
+        let path = zk_artifacts_dir(&self.project.paths.artifacts, &source)
+            .expect("Failed to get Contract filename.");
         fs::create_dir_all(&path)
             .map_err(|e| Error::msg(format!("Could not create artifacts directory: {}", e)))?;
         Ok(path)
     }
 
+    /// Returns the cache of the last build, or an empty one if every source is to be recompiled
+    /// because the build is forced or caching is disabled.
+    fn read_cache(&self, cache_path: &Path) -> ZkSolcCache {
+        if self.force || !self.cache {
+            return ZkSolcCache::default()
+        }
+        ZkSolcCache::read(cache_path)
+    }
+
     /// Builds the file path for the artifacts (compiler output) of a contract based on the
     /// contract's source file and the project's artifacts directory. The function performs the
     /// following steps to construct the artifacts file path:
@@ -772,7 +1228,1006 @@ impl ZkSolc {
     ///
     /// This function can return an error if the creation of the artifacts file fails.
     fn build_artifacts_file(&self, source: String) -> Result<File> {
-        File::create(self.project.paths.artifacts.join(source).join("artifacts.json"))
+        let artifacts_dir = zk_artifacts_dir(&self.project.paths.artifacts, Path::new(&source))
+            .ok_or_else(|| Error::msg(format!("Invalid contract filename: {source}")))?;
+        File::create(artifacts_dir.join("artifacts.json"))
             .map_err(|e| Error::msg(format!("Could not create artifacts file: {}", e)))
     }
 }
+
+/// Returns the `zkout` directory `zk-build` writes the artifacts of the project at `root` to
+pub fn zk_out_dir(root: &Path) -> PathBuf {
+    root.join("zkout")
+}
+
+/// Returns the directory in `zk_out_path` the artifacts of the given source are written to, named
+/// after its file name, or `None` if the source path has no file name
+pub fn zk_artifacts_dir(zk_out_path: &Path, source: &Path) -> Option<PathBuf> {
+    Some(zk_out_path.join(source.file_name()?))
+}
+
+/// Records the compilation unit of every source file built by the last `zk-build`, keyed by the
+/// path of the source file relative to the project root
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+struct ZkSolcCache {
+    sources: BTreeMap<String, String>,
+}
+
+impl ZkSolcCache {
+    /// Reads the cache file, returning an empty cache if it doesn't exist or is invalid
+    fn read(path: &Path) -> Self {
+        fs::read_to_string(path)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
+
+    fn write(&self, path: &Path) -> Result<()> {
+        let content = serde_json::to_string_pretty(self)
+            .map_err(|e| Error::msg(format!("Could not serialize zksolc cache: {}", e)))?;
+        fs::write(path, content)
+            .map_err(|e| Error::msg(format!("Could not write zksolc cache: {}", e)))
+    }
+
+    /// Whether the source was built from the compilation unit with the given key
+    fn is_fresh(&self, source: &str, key: &str) -> bool {
+        self.sources.get(source).map_or(false, |cached| cached == key)
+    }
+}
+
+/// Hashes the standard json input of a compilation unit, which contains the content of the source
+/// file and all of its imports, together with the compiler fingerprint
+fn unit_cache_key(standard_json: &Value, fingerprint: &str) -> String {
+    let mut preimage = fingerprint.as_bytes().to_vec();
+    preimage.extend(serde_json::to_vec(standard_json).unwrap_or_default());
+    hex::encode(keccak256(preimage))
+}
+
+/// Parses the `errors` array of the compiler output into [`ZkSolcDiagnostic`]s
+fn parse_diagnostics(output_json: &Value, source: &str) -> Vec<ZkSolcDiagnostic> {
+    let errors = output_json.get("errors").and_then(Value::as_array).cloned().unwrap_or_default();
+    errors
+        .iter()
+        .map(|error| {
+            let str_field =
+                |name: &str| error.get(name).and_then(Value::as_str).map(str::to_string);
+            let source_location = error.get("sourceLocation").and_then(|location| {
+                Some(ZkSolcSourceLocation {
+                    file: location.get("file")?.as_str()?.to_string(),
+                    start: location.get("start").and_then(Value::as_i64).unwrap_or(-1),
+                    end: location.get("end").and_then(Value::as_i64).unwrap_or(-1),
+                })
+            });
+            ZkSolcDiagnostic {
+                severity: str_field("severity").unwrap_or_else(|| "Unknown".to_string()),
+                code: str_field("errorCode"),
+                message: str_field("message")
+                    .or_else(|| str_field("formattedMessage"))
+                    .unwrap_or_default(),
+                source_location,
+                contract: source.to_string(),
+            }
+        })
+        .collect()
+}
+
+/// Returns the version of the zksolc binary at the given path, which reports it as e.g.
+/// `zksolc, the zkEVM Solidity compiler v1.3.11`
+fn zksolc_version(compiler_path: &Path) -> Result<Version> {
+    let output = Command::new(compiler_path)
+        .arg("--version")
+        .output()
+        .map_err(|e| Error::msg(format!("Could not run zksolc --version: {}", e)))?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    stdout
+        .split_whitespace()
+        .filter_map(|word| Version::parse(word.trim_start_matches('v')).ok())
+        .last()
+        .ok_or_else(|| Error::msg(format!("Could not parse the zksolc version from {stdout:?}")))
+}
+
+/// Whether the source at the given path is a standalone Yul object
+fn is_yul_source(path: &Path) -> bool {
+    matches!(path.extension(), Some(ext) if ext == "yul")
+}
+
+/// Returns the name of the top-level object of a Yul source, e.g. `Token` for `object "Token"`
+fn yul_object_name(source: &str) -> Option<&str> {
+    let (_, rest) = source.split_once("object \"")?;
+    rest.split_once('"').map(|(name, _)| name)
+}
+
+/// Returns the names of the objects nested in the Yul object with the given name, other than its
+/// deployed code `<name>_deployed`, which zkSync deploys as factory dependencies
+fn yul_factory_dependencies<'a>(source: &'a str, name: &str) -> Vec<&'a str> {
+    let deployed = format!("{name}_deployed");
+    source
+        .split("object \"")
+        .skip(1)
+        .filter_map(|rest| rest.split_once('"').map(|(object, _)| object))
+        .filter(|object| *object != name && *object != deployed)
+        .collect()
+}
+
+/// Returns the hash zkSync identifies the given bytecode by: the version byte `1`, a zero byte,
+/// the length of the bytecode in 32 byte words as two bytes, and the last 28 bytes of its sha256
+/// hash
+pub fn zk_bytecode_hash(bytecode: &[u8]) -> [u8; 32] {
+    let mut hash: [u8; 32] = Sha256::digest(bytecode).into();
+    let words = (bytecode.len() / 32) as u16;
+    hash[0] = 1;
+    hash[1] = 0;
+    hash[2..4].copy_from_slice(&words.to_be_bytes());
+    hash
+}
+
+/// Converts the output of zksolc in Yul mode, which prints the bytecode of the object, into the
+/// standard json output of a contract with the object's name in the source file at `path`.
+///
+/// zksolc 1.3 prints ``Contract `<path>` bytecode: 0x<bytecode>``, later releases print the
+/// bytecode on the line after `Binary:`. Objects with factory dependencies are rejected before
+/// compiling, see [`yul_factory_dependencies`].
+fn yul_output_json(stdout: &str, path: &str, name: &str) -> Result<Value> {
+    let mut lines = stdout.lines().map(str::trim);
+    let mut bytecode = None;
+    while let Some(line) = lines.next() {
+        if let Some((_, hex)) = line.split_once("bytecode: ") {
+            bytecode = Some(hex);
+            break
+        }
+        if line == "Binary:" {
+            bytecode = lines.next();
+            break
+        }
+    }
+    let bytecode = bytecode
+        .map(|hex| hex.trim_start_matches("0x"))
+        .ok_or_else(|| Error::msg(format!("zksolc printed no bytecode for {path}:{name}")))?;
+    let decoded = hex::decode(bytecode)
+        .map_err(|e| Error::msg(format!("Invalid bytecode for {path}:{name}: {e}")))?;
+
+    Ok(serde_json::json!({
+        "contracts": { path: { name: {
+            "abi": [],
+            "evm": { "bytecode": { "object": bytecode }, "methodIdentifiers": {} },
+            "hash": hex::encode(zk_bytecode_hash(&decoded)),
+            "factoryDependencies": {},
+        }}},
+        "errors": [],
+    }))
+}
+
+/// Returns the compiler info recorded in the artifacts of a source compiled from the given
+/// standard json input
+fn compiler_info(
+    standard_json: &Value,
+    zksolc_version: Option<&Version>,
+    solc_version: &Version,
+    system_mode: bool,
+    metadata_hash: bool,
+) -> Value {
+    let settings = &standard_json["settings"];
+    serde_json::json!({
+        "zksolcVersion": zksolc_version.map(Version::to_string),
+        "solcVersion": solc_version.to_string(),
+        "optimizer": settings["optimizer"],
+        "systemMode": system_mode,
+        "forceEVMLA": settings["forceEVMLA"],
+        "metadataHash": if metadata_hash { "keccak256" } else { "none" },
+    })
+}
+
+/// Returns the zksolc arguments disabling the metadata hash unless `append_cbor` is set.
+///
+/// solc's `settings.metadata` must be left unset for zksolc, which appends a hash of the metadata
+/// instead of the CBOR encoded metadata.
+fn metadata_hash_args(settings: &ZkSolcConfig) -> Vec<String> {
+    if settings.append_cbor {
+        return Vec::new()
+    }
+    vec!["--metadata-hash".to_string(), "none".to_string()]
+}
+
+/// Ensures the given zksolc version supports the given solc version
+fn check_solc_compatibility(zksolc_version: &Version, solc_version: &Version) -> Result<()> {
+    let zksolc = Version::new(zksolc_version.major, zksolc_version.minor, zksolc_version.patch);
+    let solc = Version::new(solc_version.major, solc_version.minor, solc_version.patch);
+    // newer zksolc releases than the ones known here are assumed to support any solc version
+    let newest_known = ZKSOLC_MAX_SOLC_VERSIONS
+        .last()
+        .map(|((major, minor, patch), _)| Version::new(*major, *minor, *patch));
+    if matches!(newest_known, Some(newest) if zksolc > newest) {
+        return Ok(())
+    }
+    let Some(max_solc) = ZKSOLC_MAX_SOLC_VERSIONS
+        .iter()
+        .rev()
+        .find(|((major, minor, patch), _)| zksolc >= Version::new(*major, *minor, *patch))
+        .map(|(_, (major, minor, patch))| Version::new(*major, *minor, *patch))
+    else {
+        return Ok(())
+    };
+    if solc < MIN_SUPPORTED_SOLC_VERSION || solc > max_solc {
+        return Err(Error::msg(format!(
+            "zksolc v{zksolc} supports solc versions {MIN_SUPPORTED_SOLC_VERSION} to {max_solc}, but solc {solc} is required. \
+             Use a supported solc version with `--use {max_solc}`, a newer zksolc with `--use-zksolc`, \
+             or skip this check with `--zk-skip-solc-version-check`"
+        )))
+    }
+    Ok(())
+}
+
+/// Whether the artifacts file exists and records the compilers and settings that produced it
+fn has_compiler_info(artifacts_file: &Path) -> bool {
+    let artifacts = fs::read_to_string(artifacts_file)
+        .ok()
+        .and_then(|content| serde_json::from_str::<Value>(&content).ok());
+    match artifacts {
+        Some(artifacts) => !artifacts[ZKSOLC_COMPILER_INFO_KEY].is_null(),
+        None => false,
+    }
+}
+
+/// Returns the libraries zksolc reported as `missingLibraries` for any contract in the compiler
+/// output, e.g. `src/Math.sol:Math`
+fn missing_libraries(output_json: &Value) -> BTreeSet<String> {
+    let Some(files) = output_json.get("contracts").and_then(Value::as_object) else {
+        return Default::default()
+    };
+    files
+        .values()
+        .filter_map(Value::as_object)
+        .flat_map(|contracts| contracts.values())
+        .filter_map(|contract| contract.get("missingLibraries").and_then(Value::as_array))
+        .flatten()
+        .filter_map(|library| library.as_str().map(str::to_string))
+        .collect()
+}
+
+/// Returns the contracts in the compiler output whose bytecode exceeds
+/// [`ZKSYNC_MAX_BYTECODE_SIZE`], as `file:Contract` along with the bytecode size in bytes
+fn oversized_contracts(output_json: &Value) -> Vec<(String, usize)> {
+    let Some(files) = output_json.get("contracts").and_then(Value::as_object) else {
+        return Vec::new()
+    };
+    files
+        .iter()
+        .filter_map(|(file, contracts)| Some((file, contracts.as_object()?)))
+        .flat_map(|(file, contracts)| contracts.iter().map(move |(name, c)| (file, name, c)))
+        .filter_map(|(file, name, contract)| {
+            let bytecode = contract["evm"]["bytecode"]["object"].as_str()?;
+            let size = bytecode.trim_start_matches("0x").len() / 2;
+            (size > ZKSYNC_MAX_BYTECODE_SIZE).then(|| (format!("{file}:{name}"), size))
+        })
+        .collect()
+}
+
+/// Sets the zksolc specific fields of the standard json `settings`, which solc doesn't know about,
+/// for the source file at the given path relative to the project root.
+fn apply_zksolc_settings(standard_json: &mut Value, settings: &ZkSolcConfig, path: &Path) {
+    standard_json["settings"]["forceEVMLA"] = settings.force_evmla_for(path).into();
+    let suppressed_errors = settings.suppressed_errors_for(path);
+    if !suppressed_errors.is_empty() {
+        standard_json["settings"]["suppressedErrors"] = suppressed_errors.into();
+    }
+    if let Some(evm_version) = settings.evm_version {
+        standard_json["settings"]["evmVersion"] = evm_version.to_string().into();
+    }
+    if let Some(mode) = &settings.optimizer_mode {
+        standard_json["settings"]["optimizer"]["mode"] = mode.as_str().into();
+    }
+    if settings.enable_eravm_extensions {
+        standard_json["settings"]["enableEraVMExtensions"] = true.into();
+    }
+}
+
+/// Enables the EraVM extensions with the system mode instead of
+/// `settings.enableEraVMExtensions`, which releases before [`ZKSOLC_ERAVM_EXTENSIONS_VERSION`]
+/// don't know about.
+fn enable_eravm_extensions_with_system_mode(standard_json: &mut Value, args: &mut Vec<String>) {
+    if let Some(settings) = standard_json.get_mut("settings").and_then(Value::as_object_mut) {
+        settings.remove("enableEraVMExtensions");
+    }
+    if !args.iter().any(|arg| arg == "--system-mode") {
+        args.push("--system-mode".to_string());
+    }
+}
+
+/// Whether the given zksolc version only enables the EraVM extensions in system mode.
+///
+/// An unknown version is assumed to be a recent one.
+fn needs_system_mode_for_extensions(zksolc_version: Option<&Version>) -> bool {
+    match zksolc_version {
+        Some(version) => {
+            Version::new(version.major, version.minor, version.patch)
+                < ZKSOLC_ERAVM_EXTENSIONS_VERSION
+        }
+        None => false,
+    }
+}
+
+/// Ensures zksolc supports targeting the given EVM version.
+fn validate_evm_version(evm_version: EvmVersion) -> Result<()> {
+    if ZKSOLC_SUPPORTED_EVM_VERSIONS.contains(&evm_version) {
+        return Ok(())
+    }
+    let supported: Vec<_> = ZKSOLC_SUPPORTED_EVM_VERSIONS.iter().map(|v| v.to_string()).collect();
+    Err(Error::msg(format!(
+        "zksolc does not support the EVM version `{evm_version}` set in `zksolc.evm_version`. Supported versions are: {}",
+        supported.join(", ")
+    )))
+}
+
+/// Ensures zksolc supports the given LLVM optimization mode.
+fn validate_optimizer_mode(mode: &str) -> Result<()> {
+    if ZKSOLC_OPTIMIZER_MODES.contains(&mode) {
+        return Ok(())
+    }
+    Err(Error::msg(format!(
+        "zksolc does not support the optimizer mode `{mode}` set in `zksolc.optimizer_mode`. Supported modes are: {}",
+        ZKSOLC_OPTIMIZER_MODES.join(", ")
+    )))
+}
+
+/// Ensures every output in the given selection is supported by zksolc.
+fn validate_output_selection(output_selection: &[String]) -> Result<()> {
+    let unsupported: Vec<_> = output_selection
+        .iter()
+        .filter(|output| !ZKSOLC_SUPPORTED_OUTPUTS.contains(&output.as_str()))
+        .cloned()
+        .collect();
+    if !unsupported.is_empty() {
+        return Err(Error::msg(format!(
+            "Unsupported zksolc output selection: {}. Supported outputs are: {}",
+            unsupported.join(", "),
+            ZKSOLC_SUPPORTED_OUTPUTS.join(", ")
+        )))
+    }
+    Ok(())
+}
+
+/// Removes the bytecode related outputs zksolc emits regardless of the output selection from
+/// every contract in the compiler output, unless `evm.bytecode` was requested.
+fn retain_selected_outputs(output_json: &mut Value, output_selection: &[String]) {
+    if output_selection.iter().any(|output| output == "evm.bytecode") {
+        return
+    }
+
+    let Some(files) = output_json.get_mut("contracts").and_then(Value::as_object_mut) else {
+        return
+    };
+    for contract in files.values_mut().filter_map(Value::as_object_mut) {
+        for output in contract.values_mut().filter_map(Value::as_object_mut) {
+            output.remove("hash");
+            output.remove("factoryDependencies");
+            if let Some(evm) = output.get_mut("evm").and_then(Value::as_object_mut) {
+                evm.remove("bytecode");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cmd::forge::zk_build::ZkBuildArgs;
+    use clap::Parser;
+    use foundry_config::{Config, ZkSolcContractSettings};
+    use serde_json::json;
+
+    /// Returns a job compiling `src/<filename>` with the given arguments and standard json input,
+    /// `.yul` files are compiled as the Yul object named after the file
+    fn job(
+        filename: &str,
+        args: impl IntoIterator<Item = impl ToString>,
+        standard_json: Value,
+    ) -> ZkSolcJob {
+        let contract_path = PathBuf::from("src").join(filename);
+        let yul_object = is_yul_source(&contract_path)
+            .then(|| contract_path.file_stem().unwrap().to_string_lossy().to_string());
+        ZkSolcJob {
+            cache_source: contract_path.to_string_lossy().to_string(),
+            contract_path,
+            filename: filename.to_string(),
+            cache_key: String::new(),
+            args: args.into_iter().map(|arg| arg.to_string()).collect(),
+            standard_json,
+            compiler_info: Value::Null,
+            yul_object,
+        }
+    }
+
+    /// Writes an executable shell script with the given body to `dir/name`, standing in for a
+    /// compiler
+    #[cfg(unix)]
+    fn stub_compiler(dir: &Path, name: &str, script: &str) -> PathBuf {
+        use std::os::unix::fs::PermissionsExt;
+
+        let path = dir.join(name);
+        fs::write(&path, format!("#!/bin/sh\n{script}")).unwrap();
+        fs::set_permissions(&path, fs::Permissions::from_mode(0o755)).unwrap();
+        path
+    }
+
+    #[test]
+    fn rejects_unsupported_output_selection() {
+        assert!(validate_output_selection(&["abi".to_string(), "evm.bytecode".to_string()]).is_ok());
+        let err = validate_output_selection(&["abi".to_string(), "evm.gasEstimates".to_string()])
+            .unwrap_err();
+        assert!(err.to_string().contains("evm.gasEstimates"));
+    }
+
+    #[test]
+    fn maps_force_evmla_to_settings() {
+        let mut input = json!({ "language": "Solidity", "settings": { "optimizer": {} } });
+        let settings = ZkSolcConfig { force_evmla: true, ..Default::default() };
+        apply_zksolc_settings(&mut input, &settings, Path::new("src/Legacy.sol"));
+        assert_eq!(input["settings"]["forceEVMLA"], json!(true));
+        assert_eq!(input["settings"]["optimizer"], json!({}));
+        assert!(input["settings"].get("metadata").is_none());
+        assert!(input["settings"].get("suppressedErrors").is_none());
+
+        apply_zksolc_settings(&mut input, &Default::default(), Path::new("src/Legacy.sol"));
+        assert_eq!(input["settings"]["forceEVMLA"], json!(false));
+    }
+
+    #[test]
+    fn maps_evm_version_to_settings() {
+        let mut input = json!({ "language": "Solidity", "settings": { "evmVersion": "paris" } });
+        apply_zksolc_settings(&mut input, &Default::default(), Path::new("src/Counter.sol"));
+        assert_eq!(input["settings"]["evmVersion"], json!("paris"));
+
+        let settings = ZkSolcConfig { evm_version: Some(EvmVersion::London), ..Default::default() };
+        apply_zksolc_settings(&mut input, &settings, Path::new("src/Counter.sol"));
+        assert_eq!(input["settings"]["evmVersion"], json!("london"));
+
+        assert!(validate_evm_version(EvmVersion::London).is_ok());
+        let err = validate_evm_version(EvmVersion::Shanghai).unwrap_err();
+        assert!(err.to_string().contains("`shanghai`"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn passes_pinned_optimizer_mode_to_zksolc() {
+        // zksolc echoing the standard json it was given
+        let dir = tempfile::tempdir().unwrap();
+        let zksolc = stub_compiler(dir.path(), "zksolc", "cat\n");
+
+        let input = json!({
+            "language": "Solidity",
+            "settings": { "optimizer": { "enabled": true, "runs": 200 } }
+        });
+        let received_optimizer = |settings: &ZkSolcConfig| {
+            let mut standard_json = input.clone();
+            apply_zksolc_settings(&mut standard_json, settings, Path::new("src/Counter.sol"));
+            let output =
+                job("Counter.sol", ["--standard-json"], standard_json).run(&zksolc).unwrap();
+            let received: Value = serde_json::from_slice(&output.stdout).unwrap();
+            received["settings"]["optimizer"].clone()
+        };
+
+        let settings = ZkSolcConfig { optimizer_mode: Some("z".to_string()), ..Default::default() };
+        assert_eq!(
+            received_optimizer(&settings),
+            json!({ "enabled": true, "runs": 200, "mode": "z" })
+        );
+
+        // without a pin, the mode is left to the zksolc version
+        assert!(received_optimizer(&Default::default()).get("mode").is_none());
+
+        assert!(validate_optimizer_mode("3").is_ok());
+        assert!(validate_optimizer_mode("fast").unwrap_err().to_string().contains("`fast`"));
+    }
+
+    #[test]
+    fn records_compiler_info() {
+        let settings = ZkSolcConfig {
+            optimizer_mode: Some("z".to_string()),
+            append_cbor: false,
+            ..Default::default()
+        };
+        let mut standard_json = json!({ "settings": { "optimizer": { "enabled": true } } });
+        apply_zksolc_settings(&mut standard_json, &settings, Path::new("src/Counter.sol"));
+
+        let info = compiler_info(
+            &standard_json,
+            Some(&Version::new(1, 3, 11)),
+            &Version::new(0, 8, 20),
+            true,
+            settings.append_cbor,
+        );
+        assert_eq!(
+            info,
+            json!({
+                "zksolcVersion": "1.3.11",
+                "solcVersion": "0.8.20",
+                "optimizer": { "enabled": true, "mode": "z" },
+                "systemMode": true,
+                "forceEVMLA": false,
+                "metadataHash": "none",
+            })
+        );
+        assert!(compiler_info(&standard_json, None, &Version::new(0, 8, 20), false, true)
+            ["zksolcVersion"]
+            .is_null());
+    }
+
+    #[test]
+    fn artifacts_without_compiler_info_are_rebuilt() {
+        let dir = tempfile::tempdir().unwrap();
+        let artifacts_file = dir.path().join("artifacts.json");
+        assert!(!has_compiler_info(&artifacts_file));
+
+        fs::write(&artifacts_file, r#"{"contracts":{},"version":"0.8.19"}"#).unwrap();
+        assert!(!has_compiler_info(&artifacts_file));
+
+        fs::write(
+            &artifacts_file,
+            r#"{"contracts":{},"version":"0.8.19","compilerInfo":{"zksolcVersion":"1.3.11"}}"#,
+        )
+        .unwrap();
+        assert!(has_compiler_info(&artifacts_file));
+    }
+
+    #[test]
+    fn maps_eravm_extensions_to_settings() {
+        let mut input = json!({ "language": "Solidity", "settings": {} });
+        apply_zksolc_settings(&mut input, &Default::default(), Path::new("src/Extension.sol"));
+        assert!(input["settings"].get("enableEraVMExtensions").is_none());
+
+        let settings = ZkSolcConfig { enable_eravm_extensions: true, ..Default::default() };
+        apply_zksolc_settings(&mut input, &settings, Path::new("src/Extension.sol"));
+        assert_eq!(input["settings"]["enableEraVMExtensions"], json!(true));
+
+        // older releases only enable the extensions in system mode
+        assert!(needs_system_mode_for_extensions(Some(&Version::new(1, 3, 11))));
+        assert!(needs_system_mode_for_extensions(Some(&Version::parse("1.4.1-dev").unwrap())));
+        assert!(!needs_system_mode_for_extensions(Some(&Version::new(1, 5, 0))));
+        assert!(!needs_system_mode_for_extensions(None));
+
+        // where the setting is replaced by the system mode
+        let mut args = vec!["--standard-json".to_string()];
+        enable_eravm_extensions_with_system_mode(&mut input, &mut args);
+        assert!(input["settings"].get("enableEraVMExtensions").is_none());
+        assert_eq!(args, ["--standard-json", "--system-mode"]);
+        enable_eravm_extensions_with_system_mode(&mut input, &mut args);
+        assert_eq!(args, ["--standard-json", "--system-mode"]);
+
+        let mut yul = json!({ "language": "Yul", "source": "" });
+        enable_eravm_extensions_with_system_mode(&mut yul, &mut args);
+        assert!(yul.get("settings").is_none());
+    }
+
+    #[test]
+    fn disabling_append_cbor_drops_metadata_hash() {
+        assert!(metadata_hash_args(&Default::default()).is_empty());
+        let settings = ZkSolcConfig { append_cbor: false, ..Default::default() };
+        assert_eq!(metadata_hash_args(&settings), ["--metadata-hash", "none"]);
+
+        let (Ok(zksolc), Ok(solc)) = (std::env::var("ZKSOLC_PATH"), std::env::var("SOLC_PATH"))
+        else {
+            // TODO: this test requires zksolc and solc to compile with.
+            return
+        };
+        // the sources only differ in their metadata, not in their code
+        let compile = |comment: &str, append_cbor: bool| {
+            let source = format!("// {comment}\ncontract Counter {{ uint256 public number; }}");
+            let mut standard_json = json!({
+                "language": "Solidity",
+                "sources": { "src/Counter.sol": { "content": source } },
+                "settings": { "outputSelection": { "*": { "*": ["abi"] } } }
+            });
+            let settings = ZkSolcConfig { append_cbor, ..Default::default() };
+            apply_zksolc_settings(&mut standard_json, &settings, Path::new("src/Counter.sol"));
+            let mut args = vec!["--standard-json".to_string(), "--solc".to_string(), solc.clone()];
+            args.extend(metadata_hash_args(&settings));
+            let output = job("Counter.sol", args, standard_json).run(Path::new(&zksolc)).unwrap();
+            let output: Value = serde_json::from_slice(&output.stdout).unwrap();
+            output["contracts"]["src/Counter.sol"]["Counter"]["evm"]["bytecode"]["object"].clone()
+        };
+        assert_ne!(compile("a", true), compile("b", true));
+        // without the metadata hash, the bytecode ends with the contract code
+        assert_eq!(compile("a", false), compile("b", false));
+    }
+
+    #[test]
+    fn suppresses_errors_only_in_scoped_sources() {
+        let settings = ZkSolcConfig {
+            contracts: BTreeMap::from([(
+                "lib/vendor".to_string(),
+                ZkSolcContractSettings {
+                    suppressed_errors: Some(vec!["sendtransfer".to_string()]),
+                    ..Default::default()
+                },
+            )]),
+            ..Default::default()
+        };
+
+        let mut vendored = json!({ "language": "Solidity", "settings": {} });
+        apply_zksolc_settings(&mut vendored, &settings, Path::new("lib/vendor/Wallet.sol"));
+        assert_eq!(vendored["settings"]["suppressedErrors"], json!(["sendtransfer"]));
+
+        // the same error still fails the build of project sources
+        let mut project = json!({ "language": "Solidity", "settings": {} });
+        apply_zksolc_settings(&mut project, &settings, Path::new("src/Wallet.sol"));
+        assert!(project["settings"].get("suppressedErrors").is_none());
+    }
+
+    #[test]
+    fn rejects_incompatible_solc_version() {
+        let zksolc = Version::new(1, 3, 11);
+        assert!(check_solc_compatibility(&zksolc, &Version::new(0, 8, 20)).is_ok());
+        assert!(check_solc_compatibility(&zksolc, &Version::new(0, 4, 12)).is_ok());
+
+        let err = check_solc_compatibility(&zksolc, &Version::new(0, 8, 21)).unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("zksolc v1.3.11 supports solc versions 0.4.12 to 0.8.20"));
+        assert!(message.contains("solc 0.8.21 is required"));
+        assert!(message.contains("--zk-skip-solc-version-check"));
+
+        assert!(check_solc_compatibility(&Version::new(1, 3, 5), &Version::new(0, 8, 18)).is_err());
+        assert!(check_solc_compatibility(&zksolc, &Version::new(0, 4, 11)).is_err());
+    }
+
+    #[test]
+    fn accepts_any_solc_version_with_newer_zksolc() {
+        let solc = Version::new(0, 8, 24);
+        assert!(check_solc_compatibility(&Version::new(1, 4, 0), &solc).is_ok());
+        assert!(check_solc_compatibility(&Version::new(1, 5, 0), &solc).is_ok());
+        assert!(check_solc_compatibility(&Version::new(1, 3, 11), &solc).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn aborts_on_stubbed_incompatible_compilers() {
+        let dir = tempfile::tempdir().unwrap();
+        let stub = |name: &str, version_output: &str| {
+            stub_compiler(dir.path(), name, &format!("printf '{version_output}\\n'\n"))
+        };
+        let zksolc = stub("zksolc", "zksolc, the zkEVM Solidity compiler v1.3.7");
+        let solc = stub(
+            "solc",
+            "solc, the solidity compiler commandline interface\\nVersion: 0.8.20+commit.a1b79de6",
+        );
+
+        let zksolc_version = zksolc_version(&zksolc).unwrap();
+        assert_eq!(zksolc_version, Version::new(1, 3, 7));
+        let solc_version = Solc::new(solc).version().unwrap();
+        let err = check_solc_compatibility(&zksolc_version, &solc_version).unwrap_err();
+        assert!(err.to_string().contains("but solc 0.8.20 is required"));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn runs_jobs_concurrently_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let zksolc = stub_compiler(
+            dir.path(),
+            "zksolc",
+            r#"cat > /dev/null
+case "$1" in
+  *Broken*) echo "unexpected end of input" >&2; exit 1 ;;
+esac
+printf '{"contracts":{"%s":{}},"errors":[]}' "$1"
+"#,
+        );
+
+        let jobs: Vec<_> = ["A", "B", "Broken", "C", "D"]
+            .into_iter()
+            .map(|name| {
+                job(&format!("{name}.sol"), ["--standard-json"], json!({ "language": "Solidity" }))
+            })
+            .collect();
+
+        let outputs = run_jobs(&zksolc, &jobs, 3);
+        assert_eq!(outputs.len(), jobs.len());
+        for (job, output) in jobs.iter().zip(&outputs) {
+            match output {
+                Ok(output) => {
+                    let output: Value = serde_json::from_slice(&output.stdout).unwrap();
+                    assert!(output["contracts"].get(job.cache_source.as_str()).is_some());
+                }
+                Err(err) => {
+                    assert_eq!(job.filename, "Broken.sol");
+                    assert!(err.to_string().contains("unexpected end of input"));
+                }
+            }
+        }
+        assert_eq!(outputs.iter().filter(|output| output.is_err()).count(), 1);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn compiles_standalone_yul_objects() {
+        let source = r#"
+            // a standalone object, not wrapped in a contract
+            object "Counter" {
+                code { datacopy(0, dataoffset("Counter_deployed"), datasize("Counter_deployed")) }
+                object "Counter_deployed" { code { mstore(0, 1) return(0, 32) } }
+            }
+        "#;
+        assert_eq!(yul_object_name(source), Some("Counter"));
+        assert!(yul_factory_dependencies(source, "Counter").is_empty());
+        assert!(is_yul_source(Path::new("src/Counter.yul")));
+        assert!(!is_yul_source(Path::new("src/Counter.sol")));
+
+        // zksolc prints the bytecode of the object in Yul mode
+        let dir = tempfile::tempdir().unwrap();
+        let bytecode = "00".repeat(32 * 3);
+        let zksolc = stub_compiler(
+            dir.path(),
+            "zksolc",
+            &format!(
+                "case \"$2\" in --yul) ;; *) exit 1 ;; esac\necho \"Contract \\`$1\\` bytecode: 0x{bytecode}\"\n"
+            ),
+        );
+
+        let job = job("Counter.yul", ["--yul", "--bin"], Value::Null);
+        assert_eq!(job.yul_object.as_deref(), Some("Counter"));
+        let output = job.run(&zksolc).unwrap();
+        let output =
+            yul_output_json(&String::from_utf8_lossy(&output.stdout), &job.cache_source, "Counter")
+                .unwrap();
+
+        let contract = &output["contracts"]["src/Counter.yul"]["Counter"];
+        assert_eq!(contract["evm"]["bytecode"]["object"], bytecode.as_str());
+        // 3 words long
+        assert!(contract["hash"].as_str().unwrap().starts_with("01000003"));
+        assert_eq!(contract["factoryDependencies"], json!({}));
+        assert!(oversized_contracts(&output).is_empty());
+
+        // later releases print the bytecode on its own line
+        let output = yul_output_json(
+            "======= src/Counter.yul =======\nBinary:\n0000\n",
+            "src/Counter.yul",
+            "Counter",
+        )
+        .unwrap();
+        assert_eq!(
+            output["contracts"]["src/Counter.yul"]["Counter"]["evm"]["bytecode"]["object"],
+            "0000"
+        );
+        assert!(yul_output_json("", "src/Counter.yul", "Counter").is_err());
+    }
+
+    #[test]
+    fn finds_nested_yul_objects() {
+        let source = r#"
+            object "Factory" {
+                code { datacopy(0, dataoffset("Factory_deployed"), datasize("Factory_deployed")) }
+                object "Factory_deployed" {
+                    code { pop(create(0, dataoffset("Child"), datasize("Child"))) }
+                    object "Child" {
+                        code { datacopy(0, dataoffset("Child_deployed"), datasize("Child_deployed")) }
+                        object "Child_deployed" { code { } }
+                    }
+                }
+            }
+        "#;
+        assert_eq!(yul_factory_dependencies(source, "Factory"), ["Child", "Child_deployed"]);
+    }
+
+    #[test]
+    fn resolves_forge_std_remappings_like_evm_builds() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("lib/forge-std/src")).unwrap();
+        fs::create_dir_all(root.join("src")).unwrap();
+        fs::write(root.join("lib/forge-std/src/Test.sol"), "contract Test {}").unwrap();
+        let counter = root.join("src/Counter.t.sol");
+        fs::write(&counter, "import \"forge-std/Test.sol\"; contract CounterTest is Test {}")
+            .unwrap();
+
+        let config = Config::load_with_root(root);
+        let evm_input = config.project().unwrap().standard_json_input(&counter).unwrap();
+
+        let mut project = config.project().unwrap();
+        project.paths.artifacts = root.join("zkout");
+        let opts = ZkSolcOpts {
+            compiler_path: root.join("zksolc"),
+            is_system: false,
+            settings: config.zksolc.clone(),
+            format_json: false,
+            skip_solc_version_check: false,
+            jobs: 1,
+            force: false,
+            cache: true,
+        };
+        let mut zksolc = ZkSolc::new(opts, project);
+        zksolc.parse_json_input(counter).unwrap();
+        let zk_input = zksolc.standard_json.unwrap();
+
+        // the auto-detected forge-std remapping resolves the import
+        let remappings = &zk_input["settings"]["remappings"];
+        assert_eq!(remappings, &serde_json::to_value(&evm_input.settings.remappings).unwrap());
+        assert!(remappings.as_array().unwrap().contains(&json!("forge-std/=lib/forge-std/src/")));
+        assert!(zk_input["sources"].get("lib/forge-std/src/Test.sol").is_some());
+        assert!(zk_input["sources"].get("src/Counter.t.sol").is_some());
+    }
+
+    #[test]
+    fn can_parse_diagnostics() {
+        let output = json!({
+            "errors": [
+                {
+                    "component": "general",
+                    "errorCode": "2072",
+                    "formattedMessage": "Warning: Unused local variable.",
+                    "message": "Unused local variable.",
+                    "severity": "warning",
+                    "sourceLocation": { "file": "src/Greeter.sol", "start": 120, "end": 131 },
+                    "type": "Warning"
+                },
+                {
+                    "component": "general",
+                    "formattedMessage": "Warning: You are using '<address payable>.send/transfer(<X>)'",
+                    "message": "You are using '<address payable>.send/transfer(<X>)'",
+                    "severity": "warning",
+                    "type": "Warning"
+                }
+            ]
+        });
+
+        let diagnostics = parse_diagnostics(&output, "Greeter.sol");
+        assert_eq!(diagnostics.len(), 2);
+        assert_eq!(
+            diagnostics[0],
+            ZkSolcDiagnostic {
+                severity: "warning".to_string(),
+                code: Some("2072".to_string()),
+                message: "Unused local variable.".to_string(),
+                source_location: Some(ZkSolcSourceLocation {
+                    file: "src/Greeter.sol".to_string(),
+                    start: 120,
+                    end: 131,
+                }),
+                contract: "Greeter.sol".to_string(),
+            }
+        );
+        assert!(diagnostics[1].source_location.is_none());
+
+        let report = serde_json::to_value(&diagnostics).unwrap();
+        assert_eq!(report[0]["sourceLocation"]["file"], "src/Greeter.sol");
+        assert_eq!(report[0]["code"], "2072");
+    }
+
+    #[test]
+    fn can_find_missing_libraries() {
+        let output = json!({
+            "contracts": {
+                "src/Calculator.sol": {
+                    "Calculator": {
+                        "hash": "0100004b",
+                        "missingLibraries": ["src/Math.sol:Math", "src/Strings.sol:Strings"]
+                    }
+                },
+                "src/Math.sol": {
+                    "Math": { "hash": "0100001d", "missingLibraries": [] }
+                }
+            }
+        });
+        assert_eq!(
+            missing_libraries(&output).into_iter().collect::<Vec<_>>(),
+            vec!["src/Math.sol:Math".to_string(), "src/Strings.sol:Strings".to_string()]
+        );
+
+        let output = json!({ "contracts": { "src/Math.sol": { "Math": { "hash": "0100001d" } } } });
+        assert!(missing_libraries(&output).is_empty());
+    }
+
+    #[test]
+    fn only_dirty_units_are_recompiled() {
+        let unit = |sources: &[(&str, &str)]| {
+            let sources: serde_json::Map<_, _> = sources
+                .iter()
+                .map(|(path, content)| (path.to_string(), json!({ "content": content })))
+                .collect();
+            json!({ "language": "Solidity", "sources": sources, "settings": {} })
+        };
+        let leaf = ("src/Leaf.sol", "contract Leaf {}");
+        let user = ("src/User.sol", "import './Leaf.sol'; contract User {}");
+        let other = ("src/Other.sol", "contract Other {}");
+
+        let mut cache = ZkSolcCache::default();
+        for (source, input) in
+            [(leaf.0, unit(&[leaf])), (user.0, unit(&[user, leaf])), (other.0, unit(&[other]))]
+        {
+            cache.sources.insert(source.to_string(), unit_cache_key(&input, "zksolc"));
+        }
+
+        // change the leaf contract, which the user contract imports
+        let leaf = ("src/Leaf.sol", "contract Leaf { function foo() external {} }");
+        let dirty: Vec<_> =
+            [(leaf.0, unit(&[leaf])), (user.0, unit(&[user, leaf])), (other.0, unit(&[other]))]
+                .into_iter()
+                .filter(|(source, input)| !cache.is_fresh(source, &unit_cache_key(input, "zksolc")))
+                .map(|(source, _)| source)
+                .collect();
+        assert_eq!(dirty, vec!["src/Leaf.sol", "src/User.sol"]);
+
+        // a different compiler invalidates every unit
+        assert!(!cache.is_fresh(other.0, &unit_cache_key(&unit(&[other]), "zksolc-1.3.14")));
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ZKSOLC_CACHE_FILENAME);
+        assert_eq!(ZkSolcCache::read(&path), ZkSolcCache::default());
+        cache.write(&path).unwrap();
+        assert_eq!(ZkSolcCache::read(&path), cache);
+    }
+
+    #[test]
+    fn force_recompiles_cached_units() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(ZKSOLC_CACHE_FILENAME);
+        let unit = json!({ "language": "Solidity", "sources": {}, "settings": {} });
+        let mut cache = ZkSolcCache::default();
+        cache.sources.insert("src/Counter.sol".to_string(), unit_cache_key(&unit, "zksolc"));
+        cache.write(&path).unwrap();
+
+        let zksolc = |force: bool, cache: bool| {
+            let opts = ZkSolcOpts {
+                compiler_path: dir.path().join("zksolc"),
+                is_system: false,
+                settings: Default::default(),
+                format_json: false,
+                skip_solc_version_check: false,
+                jobs: 1,
+                force,
+                cache,
+            };
+            ZkSolc::new(opts, Config::load_with_root(dir.path()).project().unwrap())
+        };
+        let is_fresh = |zksolc: ZkSolc| {
+            zksolc.read_cache(&path).is_fresh("src/Counter.sol", &unit_cache_key(&unit, "zksolc"))
+        };
+        assert!(is_fresh(zksolc(false, true)));
+        // `--force` and `cache = false` recompile the unchanged unit
+        assert!(!is_fresh(zksolc(true, true)));
+        assert!(!is_fresh(zksolc(false, false)));
+
+        let args = ZkBuildArgs::parse_from(["foundry-cli", "--force"]);
+        assert!(Config::from(&args).force);
+    }
+
+    #[test]
+    fn flags_oversized_contracts() {
+        // a large constructor, e.g. one initializing big constant arrays, grows the bytecode
+        let oversized = "00".repeat(ZKSYNC_MAX_BYTECODE_SIZE + 64);
+        let output = json!({
+            "contracts": {
+                "src/Big.sol": {
+                    "Big": { "evm": { "bytecode": { "object": oversized } } },
+                    "Small": { "evm": { "bytecode": { "object": "0000008003" } } }
+                },
+                "src/Abi.sol": { "Abi": { "abi": [] } }
+            }
+        });
+
+        let flagged = oversized_contracts(&output);
+        assert_eq!(flagged, vec![("src/Big.sol:Big".to_string(), ZKSYNC_MAX_BYTECODE_SIZE + 64)]);
+        assert_eq!(ZKSYNC_MAX_BYTECODE_SIZE, 2_097_120);
+    }
+
+    #[test]
+    fn abi_only_selection_drops_bytecode() {
+        let mut output = json!({
+            "contracts": {
+                "src/Greeter.sol": {
+                    "Greeter": {
+                        "abi": [],
+                        "evm": { "bytecode": { "object": "0x00" } },
+                        "hash": "0x0100",
+                        "factoryDependencies": {}
+                    }
+                }
+            }
+        });
+
+        retain_selected_outputs(&mut output, &["abi".to_string()]);
+
+        let contract = &output["contracts"]["src/Greeter.sol"]["Greeter"];
+        assert!(contract.get("abi").is_some());
+        assert!(contract["evm"].get("bytecode").is_none());
+        assert!(contract.get("hash").is_none());
+    }
+}
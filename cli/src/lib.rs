@@ -3,4 +3,6 @@ pub mod handler;
 pub mod opts;
 pub mod stdin;
 pub mod suggestions;
+#[cfg(test)]
+mod test_utils;
 pub mod utils;
@@ -1,10 +1,30 @@
 use super::{EtherscanOpts, RpcOpts};
 use crate::{
     cmd::cast::{
-        access_list::AccessListArgs, bind::BindArgs, call::CallArgs, create2::Create2Args,
-        estimate::EstimateArgs, find_block::FindBlockArgs, interface::InterfaceArgs,
-        logs::LogsArgs, rpc::RpcArgs, run::RunArgs, send::SendTxArgs, storage::StorageArgs,
-        wallet::WalletSubcommands, zk_deposit::ZkDepositTxArgs, zk_send::ZkSendTxArgs,
+        access_list::AccessListArgs,
+        bind::BindArgs,
+        call::CallArgs,
+        create2::Create2Args,
+        estimate::EstimateArgs,
+        find_block::FindBlockArgs,
+        interface::InterfaceArgs,
+        logs::LogsArgs,
+        rpc::RpcArgs,
+        run::RunArgs,
+        send::SendTxArgs,
+        storage::StorageArgs,
+        wallet::WalletSubcommands,
+        zk_address::{ZkComputeAddressArgs, ZkCreate2Args},
+        zk_block::ZkBlockArgs,
+        zk_bytecode_by_hash::ZkBytecodeByHashArgs,
+        zk_deposit::ZkDepositTxArgs,
+        zk_gas_price::ZkGasPriceArgs,
+        zk_logs::ZkLogsArgs,
+        zk_network::ZkNetworkArgs,
+        zk_send::ZkSendTxArgs,
+        zk_sign::ZkSignTypedDataArgs,
+        zk_tokens::ZkConfirmedTokensArgs,
+        zk_withdraw::ZkWithdrawArgs,
     },
     utils::parse_u256,
 };
@@ -440,6 +460,46 @@ pub enum Subcommands {
     #[clap(about = "Bridge Assets from L1 to L2.")]
     ZkDepositTx(ZkDepositTxArgs),
 
+    /// Compute the zkSync contract address from a given deployment nonce and deployer address.
+    #[clap(name = "zk-compute-address", visible_aliases = ["zkca"])]
+    ZkComputeAddress(ZkComputeAddressArgs),
+
+    /// Compute the zkSync contract address of a CREATE2 deployment.
+    #[clap(name = "zk-create2", visible_aliases = ["zkc2"])]
+    ZkCreate2(ZkCreate2Args),
+
+    /// List the tokens bridged to zkSync.
+    #[clap(name = "zk-confirmed-tokens", visible_aliases = ["zkct"])]
+    ZkConfirmedTokens(ZkConfirmedTokensArgs),
+
+    /// Sign EIP-712 typed data under the zkSync domain.
+    #[clap(name = "zk-sign-typed-data", visible_aliases = ["zkstd"])]
+    ZkSignTypedData(ZkSignTypedDataArgs),
+
+    /// Get the L2 gas price along with the gas per pubdata byte and the L1 gas price.
+    #[clap(name = "zk-gas-price", visible_aliases = ["zkgp"])]
+    ZkGasPrice(ZkGasPriceArgs),
+
+    /// Initiate a withdrawal from zkSync to L1.
+    #[clap(name = "zk-withdraw", visible_aliases = ["zkw"])]
+    ZkWithdraw(ZkWithdrawArgs),
+
+    /// Print the name and canonical endpoints of a zkSync-stack network.
+    #[clap(name = "zk-network", visible_aliases = ["zkn"])]
+    ZkNetwork(ZkNetworkArgs),
+
+    /// Get logs by signature or topic, bounded by L1 batches.
+    #[clap(name = "zk-logs", visible_aliases = ["zkl"])]
+    ZkLogs(ZkLogsArgs),
+
+    /// Get information about a block, with the full zkSync transaction objects.
+    #[clap(name = "zk-block", visible_aliases = ["zkbl"])]
+    ZkBlock(ZkBlockArgs),
+
+    /// Get the bytecode of a zkSync bytecode hash.
+    #[clap(name = "zk-bytecode-by-hash", visible_aliases = ["zkbh"])]
+    ZkBytecodeByHash(ZkBytecodeByHashArgs),
+
     /// Publish a raw transaction to the network.
     #[clap(name = "publish", visible_alias = "p")]
     PublishTx {
@@ -0,0 +1,58 @@
+//! Helpers shared by the unit tests of the commands
+
+use serde_json::{json, Value};
+use std::{
+    io::{BufRead, BufReader, Read, Write},
+    net::TcpListener,
+    thread,
+};
+
+/// Spawns a minimal HTTP server on localhost and returns its url.
+///
+/// Every request is answered with the JSON the handler returns for the JSON body of the request,
+/// `None` for requests without a body.
+pub fn spawn_json_server(handler: impl Fn(Option<Value>) -> Value + Send + 'static) -> String {
+    let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+    let url = format!("http://{}", listener.local_addr().unwrap());
+    thread::spawn(move || {
+        for stream in listener.incoming() {
+            let mut stream = stream.unwrap();
+            let mut reader = BufReader::new(stream.try_clone().unwrap());
+            let mut content_length = 0;
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if line == "\r\n" {
+                    break
+                }
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+            }
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            let request = (!body.is_empty()).then(|| serde_json::from_slice(&body).unwrap());
+
+            let response = handler(request).to_string();
+            write!(
+                stream,
+                "HTTP/1.1 200 OK\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                response.len(),
+                response
+            )
+            .unwrap();
+        }
+    });
+    url
+}
+
+/// Spawns a minimal JSON-RPC node on localhost and returns its url.
+///
+/// Every call is answered with the result the handler returns for its method and params.
+pub fn spawn_rpc_node(handler: impl Fn(&str, &Value) -> Value + Send + 'static) -> String {
+    spawn_json_server(move |request| {
+        let request = request.expect("JSON-RPC requests have a body");
+        let result = handler(request["method"].as_str().unwrap_or_default(), &request["params"]);
+        json!({ "jsonrpc": "2.0", "id": request["id"], "result": result })
+    })
+}
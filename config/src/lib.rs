@@ -67,6 +67,9 @@ pub use error::SolidityErrorCode;
 pub mod doc;
 pub use doc::DocConfig;
 
+pub mod zksolc;
+pub use zksolc::{ZkSolcConfig, ZkSolcContractSettings};
+
 mod warning;
 pub use warning::*;
 
@@ -346,6 +349,8 @@ pub struct Config {
     pub fmt: FormatterConfig,
     /// Configuration for `forge doc`
     pub doc: DocConfig,
+    /// Configuration for `forge zk-build`
+    pub zksolc: ZkSolcConfig,
     /// Configures the permissions of cheat codes that touch the file system.
     ///
     /// This includes what operations can be executed (read, write)
@@ -395,7 +400,7 @@ impl Config {
 
     /// Standalone sections in the config which get integrated into the selected profile
     pub const STANDALONE_SECTIONS: &'static [&'static str] =
-        &["rpc_endpoints", "etherscan", "fmt", "doc", "fuzz", "invariant"];
+        &["rpc_endpoints", "etherscan", "fmt", "doc", "fuzz", "invariant", "zksolc"];
 
     /// File name of config toml file
     pub const FILE_NAME: &'static str = "foundry.toml";
@@ -1795,6 +1800,7 @@ impl Default for Config {
             build_info_path: None,
             fmt: Default::default(),
             doc: Default::default(),
+            zksolc: Default::default(),
             __non_exhaustive: (),
             __warnings: vec![],
         }
@@ -4079,6 +4085,95 @@ mod tests {
         });
     }
 
+    #[test]
+    fn test_zksolc_config() {
+        figment::Jail::expect_with(|jail| {
+            jail.create_file(
+                "foundry.toml",
+                r#"
+                [zksolc]
+                output_selection = ["abi"]
+                force_evmla = true
+                append_cbor = false
+                fail_on_missing_libraries = true
+                fail_on_bytecode_size_limit = true
+                suppressed_errors = ["assemblycreate"]
+                evm_version = "london"
+                optimizer_mode = "z"
+                enable_eravm_extensions = true
+
+                [zksolc.contracts."src/Yul.sol"]
+                force_evmla = false
+
+                [zksolc.contracts."lib/vendor"]
+                suppressed_errors = ["sendtransfer"]
+
+                [zksolc.contracts."lib/vendor/Token.sol"]
+                suppressed_errors = []
+            "#,
+            )?;
+            let loaded = Config::load().sanitized();
+            assert_eq!(
+                loaded.zksolc,
+                ZkSolcConfig {
+                    output_selection: vec!["abi".to_string()],
+                    force_evmla: true,
+                    append_cbor: false,
+                    fail_on_missing_libraries: true,
+                    fail_on_bytecode_size_limit: true,
+                    suppressed_errors: vec!["assemblycreate".to_string()],
+                    evm_version: Some(EvmVersion::London),
+                    optimizer_mode: Some("z".to_string()),
+                    enable_eravm_extensions: true,
+                    contracts: BTreeMap::from([
+                        (
+                            "src/Yul.sol".to_string(),
+                            ZkSolcContractSettings {
+                                force_evmla: Some(false),
+                                ..Default::default()
+                            }
+                        ),
+                        (
+                            "lib/vendor".to_string(),
+                            ZkSolcContractSettings {
+                                suppressed_errors: Some(vec!["sendtransfer".to_string()]),
+                                ..Default::default()
+                            }
+                        ),
+                        (
+                            "lib/vendor/Token.sol".to_string(),
+                            ZkSolcContractSettings {
+                                suppressed_errors: Some(vec![]),
+                                ..Default::default()
+                            }
+                        ),
+                    ]),
+                }
+            );
+            assert!(loaded.zksolc.force_evmla_for("src/Legacy.sol"));
+            assert!(!loaded.zksolc.force_evmla_for("src/Yul.sol"));
+            assert!(loaded.zksolc.force_evmla_for("lib/vendor/Token.sol"));
+
+            // scoped suppressions are merged with the global ones, the most specific scope wins
+            assert_eq!(loaded.zksolc.suppressed_errors_for("src/Yul.sol"), vec!["assemblycreate"]);
+            assert_eq!(
+                loaded.zksolc.suppressed_errors_for("lib/vendor/Old.sol"),
+                vec!["assemblycreate", "sendtransfer"]
+            );
+            assert_eq!(
+                loaded.zksolc.suppressed_errors_for("lib/vendor/Token.sol"),
+                vec!["assemblycreate"]
+            );
+            // only whole path components match a directory
+            assert_eq!(
+                loaded.zksolc.suppressed_errors_for("lib/vendored/Old.sol"),
+                vec!["assemblycreate"]
+            );
+
+            Ok(())
+        });
+    }
+
     #[test]
     fn test_invariant_config() {
         figment::Jail::expect_with(|jail| {
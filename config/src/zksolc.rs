@@ -0,0 +1,117 @@
+//! Configuration specific to the `forge zk-build` command and the zksolc compiler
+
+use ethers_solc::EvmVersion;
+use serde::{Deserialize, Serialize};
+use std::{collections::BTreeMap, path::Path};
+
+/// Contains the config for compiling contracts with zksolc
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkSolcConfig {
+    /// The contract level outputs requested from zksolc.
+    ///
+    /// These are mapped to the standard json `settings.outputSelection` for every contract.
+    pub output_selection: Vec<String>,
+    /// Whether to forcibly switch to the EVM legacy assembly pipeline, mapped to the standard
+    /// json `settings.forceEVMLA`
+    pub force_evmla: bool,
+    /// Whether the metadata hash is appended to the bytecode.
+    ///
+    /// Disabling it passes `--metadata-hash none` to zksolc, so the bytecode ends with the contract
+    /// code.
+    pub append_cbor: bool,
+    /// Whether the build fails if zksolc reports libraries that are not linked
+    pub fail_on_missing_libraries: bool,
+    /// Whether the build fails if a contract's bytecode exceeds the size zkSync can deploy,
+    /// instead of only warning about it
+    pub fail_on_bytecode_size_limit: bool,
+    /// The zksolc errors suppressed for all sources, mapped to the standard json
+    /// `settings.suppressedErrors`, e.g. `sendtransfer`
+    pub suppressed_errors: Vec<String>,
+    /// The EVM version the zksolc frontend targets, mapped to the standard json
+    /// `settings.evmVersion`.
+    ///
+    /// Defaults to the `evm_version` of the profile.
+    pub evm_version: Option<EvmVersion>,
+    /// The LLVM optimization mode zksolc generates code with, mapped to the standard json
+    /// `settings.optimizer.mode`: one of `0`, `1`, `2`, `3`, `s` or `z`.
+    ///
+    /// Defaults to the mode of the zksolc version, which may change between releases.
+    pub optimizer_mode: Option<String>,
+    /// Whether the EraVM extensions, e.g. the `verbatim` instructions of the zkEVM, are available
+    /// to the contracts, mapped to the standard json `settings.enableEraVMExtensions`.
+    ///
+    /// zksolc releases before 1.5.0, which includes every release forge installs, don't know the
+    /// setting: for them this option turns on the system contract compilation mode for every
+    /// source instead, which enables the extensions.
+    pub enable_eravm_extensions: bool,
+    /// Settings overriding the ones above for individual source files or directories, keyed by
+    /// the path relative to the project root, e.g. `src/Legacy.sol` or `lib/vendor`.
+    ///
+    /// If several entries match a source file, the most specific one that sets a value wins.
+    pub contracts: BTreeMap<String, ZkSolcContractSettings>,
+}
+
+impl ZkSolcConfig {
+    /// Returns the settings of all entries matching the given source file, relative to the project
+    /// root, ordered from the most to the least specific one
+    pub fn scoped_settings(&self, path: impl AsRef<Path>) -> Vec<&ZkSolcContractSettings> {
+        let path = path.as_ref();
+        let mut matching: Vec<_> =
+            self.contracts.iter().filter(|(scope, _)| path.starts_with(scope)).collect();
+        matching.sort_by_key(|(scope, _)| std::cmp::Reverse(Path::new(scope).components().count()));
+        matching.into_iter().map(|(_, settings)| settings).collect()
+    }
+
+    /// Whether the EVM legacy assembly pipeline should be used for the given source file
+    pub fn force_evmla_for(&self, path: impl AsRef<Path>) -> bool {
+        self.scoped_settings(path)
+            .into_iter()
+            .find_map(|s| s.force_evmla)
+            .unwrap_or(self.force_evmla)
+    }
+
+    /// Returns the zksolc errors suppressed for the given source file: the global ones merged
+    /// with the ones of the most specific entry that sets `suppressed_errors`
+    pub fn suppressed_errors_for(&self, path: impl AsRef<Path>) -> Vec<String> {
+        let mut suppressed = self.suppressed_errors.clone();
+        if let Some(scoped) =
+            self.scoped_settings(path).into_iter().find_map(|s| s.suppressed_errors.as_ref())
+        {
+            suppressed
+                .extend(scoped.iter().filter(|e| !self.suppressed_errors.contains(e)).cloned());
+        }
+        suppressed
+    }
+}
+
+impl Default for ZkSolcConfig {
+    fn default() -> Self {
+        Self {
+            output_selection: vec![
+                "abi".to_string(),
+                "evm.methodIdentifiers".to_string(),
+                "evm.bytecode".to_string(),
+            ],
+            force_evmla: false,
+            append_cbor: true,
+            fail_on_missing_libraries: false,
+            fail_on_bytecode_size_limit: false,
+            suppressed_errors: Vec::new(),
+            evm_version: None,
+            optimizer_mode: None,
+            enable_eravm_extensions: false,
+            contracts: Default::default(),
+        }
+    }
+}
+
+/// zksolc settings of a single source file or directory
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ZkSolcContractSettings {
+    /// Overrides `zksolc.force_evmla` for this scope
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub force_evmla: Option<bool>,
+    /// Errors suppressed in this scope, in addition to `zksolc.suppressed_errors`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub suppressed_errors: Option<Vec<String>>,
+}
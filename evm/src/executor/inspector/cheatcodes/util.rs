@@ -7,6 +7,7 @@ use crate::{
     },
     utils::{h160_to_b160, h256_to_u256_be, ru256_to_u256, u256_to_ru256},
 };
+use blake2::{Blake2s256, Digest as _};
 use bytes::{BufMut, Bytes, BytesMut};
 use ethers::{
     abi::{AbiEncode, Address, ParamType, Token},
@@ -102,6 +103,34 @@ fn addr(private_key: U256) -> Result {
     Ok(addr.encode().into())
 }
 
+/// Derives the key under which the era VM stores `slot` of `account`:
+/// `blake2s256(pad32(account) ++ slot)`, like zksync-era's `StorageKey::raw_hashed_key`
+fn zk_storage_key(account: Address, slot: [u8; 32]) -> Result {
+    let mut preimage = [0u8; 64];
+    preimage[12..32].copy_from_slice(account.as_bytes());
+    preimage[32..].copy_from_slice(&slot);
+    Ok(H256::from_slice(&Blake2s256::digest(preimage)).encode().into())
+}
+
+/// Derives the address the zkSync ContractDeployer deploys to with CREATE2:
+/// `keccak256(keccak256("zksyncCreate2") ++ pad32(deployer) ++ salt ++ bytecodeHash ++
+/// constructorInputHash)[12..]`
+fn zk_compute_create2_address(
+    deployer: Address,
+    salt: [u8; 32],
+    bytecode_hash: [u8; 32],
+    constructor_input_hash: [u8; 32],
+) -> Result {
+    let mut preimage = [0u8; 160];
+    preimage[..32].copy_from_slice(&utils::keccak256(b"zksyncCreate2"));
+    preimage[44..64].copy_from_slice(deployer.as_bytes());
+    preimage[64..96].copy_from_slice(&salt);
+    preimage[96..128].copy_from_slice(&bytecode_hash);
+    preimage[128..].copy_from_slice(&constructor_input_hash);
+    let address = Address::from_slice(&utils::keccak256(preimage)[12..]);
+    Ok(address.encode().into())
+}
+
 fn sign(private_key: U256, digest: H256, chain_id: U256) -> Result {
     let key = parse_private_key(private_key)?;
     let wallet = LocalWallet::from(key).with_chain_id(chain_id.as_u64());
@@ -223,6 +252,10 @@ pub fn apply<DB: Database>(
     Some(match call {
         HEVMCalls::Addr(inner) => addr(inner.0),
         HEVMCalls::Sign(inner) => sign(inner.0, inner.1.into(), data.env.cfg.chain_id.into()),
+        HEVMCalls::ZkStorageKey(inner) => zk_storage_key(inner.0, inner.1),
+        HEVMCalls::ZkComputeCreate2Address(inner) => {
+            zk_compute_create2_address(inner.0, inner.1, inner.2, inner.3)
+        }
         HEVMCalls::DeriveKey0(inner) => {
             derive_key::<English>(&inner.0, DEFAULT_DERIVATION_PATH_PREFIX, inner.1)
         }
@@ -608,4 +608,10 @@ interface Vm {
 
     // Gets the map key and parent of a mapping at a given slot, for a given address.
     function getMappingKeyAndParentOf(address target, bytes32 slot) external returns (bool, bytes32, bytes32);
+
+    // Gets the key under which the era VM stores a given slot, for a given address.
+    function zkStorageKey(address account, bytes32 slot) external returns (bytes32);
+
+    // Computes the address the ContractDeployer deploys a contract to with CREATE2 on zkSync.
+    function zkComputeCreate2Address(address deployer, bytes32 salt, bytes32 bytecodeHash, bytes32 constructorInputHash) external returns (address);
 }
@@ -0,0 +1,37 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract ZkComputeCreate2AddressTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    address constant DEPLOYER = 0x36615Cf349d7F6344891B1e7CA7C72883F5dc049;
+    bytes32 constant BYTECODE_HASH = 0x010001cb6a6e8d5f6829522f19fa9568660e0a9cd53b2e8be4deb0a679452e41;
+
+    function testZkComputeCreate2Address() public {
+        // the address `cast zk-create2` derives for the same inputs
+        address predicted = vm.zkComputeCreate2Address(DEPLOYER, bytes32(uint256(1)), BYTECODE_HASH, keccak256(hex"01"));
+        assertEq(predicted, 0x78Ee9Dea03a39f5CC04C80a575517fF5de02ec4C, "address did not match");
+    }
+
+    function testZkComputeCreate2AddressWithoutConstructorInput() public {
+        address predicted = vm.zkComputeCreate2Address(DEPLOYER, bytes32(uint256(1)), BYTECODE_HASH, keccak256(""));
+        assertEq(predicted, 0x73a1D763E16AD377B328CC6593bD0d5e524B6A0f, "address did not match");
+    }
+
+    function testZkComputeCreate2AddressMatchesDerivation(
+        address deployer,
+        bytes32 salt,
+        bytes32 bytecodeHash,
+        bytes32 constructorInputHash
+    ) public {
+        bytes32 hash = keccak256(
+            bytes.concat(keccak256("zksyncCreate2"), bytes32(uint256(uint160(deployer))), salt, bytecodeHash, constructorInputHash)
+        );
+        assertEq(
+            vm.zkComputeCreate2Address(deployer, salt, bytecodeHash, constructorInputHash), address(uint160(uint256(hash)))
+        );
+    }
+}
@@ -0,0 +1,24 @@
+// SPDX-License-Identifier: Unlicense
+pragma solidity 0.8.18;
+
+import "ds-test/test.sol";
+import "./Vm.sol";
+
+contract ZkStorageKeyTest is DSTest {
+    Vm constant vm = Vm(HEVM_ADDRESS);
+
+    function testZkStorageKey() public {
+        // blake2s256 of the padded address and the slot
+        bytes32 key = vm.zkStorageKey(0x36615Cf349d7F6344891B1e7CA7C72883F5dc049, bytes32(0));
+        assertEq(key, 0x6b9dc8829d1836209b98d39673aab0b42422e2b0a0de56bc0dfb57ebb0a58ad4, "storage key did not match");
+    }
+
+    function testZkStorageKeyOfZeroAddress() public {
+        bytes32 key = vm.zkStorageKey(address(0), bytes32(0));
+        assertEq(key, 0xae09db7cd54f42b490ef09b6bc541af688e4959bb8c53f359a6f56e38ab454a3, "storage key did not match");
+    }
+
+    function testZkStorageKeyDiffersPerAccount() public {
+        assertTrue(vm.zkStorageKey(address(1), bytes32(0)) != vm.zkStorageKey(address(2), bytes32(0)));
+    }
+}